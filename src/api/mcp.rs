@@ -1,31 +1,55 @@
 use crate::tools::registry::Registry;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::Json;
 use serde_json::{json, Value as J};
 use std::io::{self, BufRead, Write};
+use std::time::Instant;
+use tracing::Instrument;
 
 use crate::core::error::GatewayError;
+use crate::core::mcp::RpcReq;
 use crate::core::mcp::{err as rpc_err, ok as rpc_ok};
-use crate::core::mcp::{RpcReq, RpcResp};
+use crate::infra::http::auth::{self, Principal};
 use crate::infra::http::json as http_json;
+use crate::infra::logging;
+use crate::infra::metrics::metrics;
 
-fn tools_list(reg: &Registry) -> J {
-    let tools: Vec<J> = reg.0.values().map(|t| {
-        json!({ "name": t.name(), "description": t.description(), "inputSchema": t.input_schema() })
-    }).collect();
+/// Tools in `reg` that `principal` may use, sorted by name.
+fn tools_list(reg: &Registry, principal: Option<&Principal>) -> J {
+    let mut tools: Vec<_> = reg
+        .0
+        .values()
+        .filter(|t| auth::permits(principal, t.name()))
+        .collect();
+    tools.sort_by_key(|t| t.name());
+    let tools: Vec<J> = tools
+        .into_iter()
+        .map(|t| {
+            let mut v = json!({ "name": t.name(), "description": t.description(), "inputSchema": t.input_schema() });
+            if let Some(out) = t.output_schema() {
+                v["outputSchema"] = out;
+            }
+            v
+        })
+        .collect();
     json!({ "tools": tools })
 }
 
-async fn call_tool(reg: &Registry, params: &J) -> Result<J, String> {
+async fn call_tool(reg: &Registry, params: &J) -> Result<J, GatewayError> {
     let name = params
         .get("name")
         .and_then(|v| v.as_str())
-        .ok_or("missing tool name")?;
+        .ok_or_else(|| GatewayError::InvalidParams("missing tool name".into()))?;
     let tool = reg
         .0
         .get(name)
-        .ok_or_else(|| format!("unknown tool: {name}"))?;
+        .ok_or_else(|| GatewayError::NotFound(format!("unknown tool: {name}")))?;
     let args = params.get("arguments").unwrap_or(&J::Null);
-    tool.call(args).await.map_err(|e| e.to_string())
+    tracing::debug!(tool = name, arguments = %logging::scrub(args), "tools.call");
+    tool.call(args)
+        .instrument(tracing::info_span!("tool.call", tool = name))
+        .await
 }
 
 // Testable helper mirroring stdio branch handling for a single line.
@@ -36,14 +60,14 @@ pub async fn handle_stdio_line(reg: &Registry, line: &str) -> String {
         Ok(r) => {
             let id = r.id.clone();
             match r.method.as_str() {
-                "tools.list" | "tools/list" => rpc_ok(id, tools_list(reg)),
+                "tools.list" | "tools/list" => rpc_ok(id, tools_list(reg, None)),
                 "initialize" => rpc_ok(
                     id,
                     json!({ "serverInfo": { "name": "irish-mcp-gateway", "version": "0.1.0" }, "capabilities": {} }),
                 ),
                 "tools.call" | "tools/call" => match call_tool(reg, &r.params).await {
                     Ok(out) => rpc_ok(id, out),
-                    Err(e) => rpc_err(id, -32000, e, None),
+                    Err(e) => rpc_err(id, e.rpc_code(), e.to_string(), e.rpc_data()),
                 },
                 _ => rpc_err(id, -32601, format!("unknown method: {}", r.method), None),
             }
@@ -53,13 +77,19 @@ pub async fn handle_stdio_line(reg: &Registry, line: &str) -> String {
     serde_json::to_string(&resp).unwrap()
 }
 
-// HTTP handler
+// HTTP handler. Tool errors carry their HTTP status (and `Retry-After` when the
+// error has one); everything else is 200 with a JSON-RPC envelope.
 pub async fn http(
     axum::extract::State(reg): axum::extract::State<Registry>,
+    principal: Option<axum::Extension<Principal>>,
     Json(req): Json<RpcReq>,
-) -> Json<RpcResp> {
+) -> Response {
     tracing::debug!(method = %req.method, id = ?req.id, "HTTP handler invoked");
+    let started = Instant::now();
     let id = req.id.clone();
+    let mut status = StatusCode::OK;
+    let mut retry_after = None;
+    let mut outcome = "ok";
     let resp = match req.method.as_str() {
         "initialize" => http_json::ok(
             id.clone(),
@@ -67,26 +97,54 @@ pub async fn http(
         ).0,
         "shutdown" => http_json::ok(id.clone(), J::Null).0,
         "tools.list" | "tools/list" => {
-            let resp = http_json::ok(id.clone(), tools_list(&reg)).0;
+            let resp = http_json::ok(
+                id.clone(),
+                tools_list(&reg, principal.as_ref().map(|p| &p.0)),
+            )
+            .0;
             tracing::trace!(response = ?resp, "tools.list response");
             resp
         }
-        "tools.call" | "tools/call" => match call_tool(&reg, &req.params).await {
+        "tools.call" | "tools/call" => match call_tool(&reg, &req.params)
+            .instrument(tracing::info_span!("mcp.request", rpc.method = "tools/call"))
+            .await
+        {
             Ok(out) => {
                 let resp = http_json::ok(id.clone(), out).0;
-                tracing::trace!(response = ?resp, "tools.call ok response");
+                tracing::trace!(response = %logging::scrub(&resp), "tools.call ok response");
                 resp
             }
             Err(e) => {
-                let resp = http_json::from_gateway_error(id.clone(), GatewayError::Message(e)).0;
-                tracing::warn!(response = ?resp, "tools.call error response");
+                status = e.http_status();
+                retry_after = e.retry_after_secs();
+                outcome = e.kind();
+                let resp = http_json::from_gateway_error(id.clone(), e).0;
+                tracing::warn!(response = %logging::scrub(&resp), "tools.call error response");
                 resp
             }
         },
         _ => http_json::error(id.clone(), -32601, format!("unknown method: {}", req.method)).0,
     };
-    tracing::debug!(response = ?resp, "HTTP handler completed");
-    Json(resp)
+    tracing::debug!(response = %logging::scrub(&resp), "HTTP handler completed");
+    let (method, tool) = match req.method.as_str() {
+        "tools.call" | "tools/call" => {
+            let name = req.params.get("name").and_then(|n| n.as_str());
+            ("tools/call", name.map_or("", |n| reg.tool_label(n)))
+        }
+        "tools.list" | "tools/list" => ("tools/list", ""),
+        m @ ("initialize" | "shutdown") => (m, ""),
+        _ => ("unknown", ""),
+    };
+    metrics().observe_request(method, tool, outcome, started.elapsed());
+    match retry_after {
+        Some(secs) => (
+            status,
+            [(header::RETRY_AFTER, secs.to_string())],
+            Json(resp),
+        )
+            .into_response(),
+        None => (status, Json(resp)).into_response(),
+    }
 }
 
 // Stdio loop
@@ -110,14 +168,14 @@ pub async fn stdio_loop(reg: Registry) -> anyhow::Result<()> {
             Ok(r) => {
                 let id = r.id.clone();
                 match r.method.as_str() {
-                    "tools.list" | "tools/list" => rpc_ok(id, tools_list(&reg)),
+                    "tools.list" | "tools/list" => rpc_ok(id, tools_list(&reg, None)),
                     "initialize" => rpc_ok(
                         id,
                         json!({ "serverInfo": { "name": "irish-mcp-gateway", "version": "0.1.0" }, "capabilities": {} }),
                     ),
                     "tools.call" | "tools/call" => match call_tool(&reg, &r.params).await {
                         Ok(out) => rpc_ok(id, out),
-                        Err(e) => rpc_err(id, -32000, e, None),
+                        Err(e) => rpc_err(id, e.rpc_code(), e.to_string(), e.rpc_data()),
                     },
                     _ => rpc_err(id, -32601, format!("unknown method: {}", r.method), None),
                 }
@@ -135,6 +193,8 @@ pub async fn stdio_loop(reg: Registry) -> anyhow::Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::infra::config::Config;
+    use crate::tools::registry::build_registry;
     use axum::body::{to_bytes, Body};
     use axum::{routing::post, Router};
     use hyper::Request;
@@ -144,7 +204,7 @@ mod tests {
     const BODY_LIMIT: usize = 1024 * 1024;
 
     fn router_with_state() -> Router {
-        let reg = crate::tools::registry::build_registry();
+        let reg = build_registry(&Config::default(), None);
         Router::new()
             .route("/mcp", post(super::http))
             .with_state(reg)
@@ -152,15 +212,26 @@ mod tests {
 
     #[test]
     fn tools_list_returns_expected_shape() {
-        let reg = crate::tools::registry::build_registry();
-        let v = super::tools_list(&reg);
+        let reg = build_registry(&Config::default(), None);
+        let v = super::tools_list(&reg, None);
         assert!(v["tools"].is_array());
-        assert_eq!(v["tools"][0]["name"], "gael.spellcheck.v1");
+        let names: Vec<&str> = v["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert!(names.contains(&"gael.spellcheck.v1"));
+        assert!(names.contains(&"hello.echo"));
+        assert!(names.windows(2).all(|w| w[0] <= w[1]));
+        for tool in v["tools"].as_array().unwrap() {
+            assert_eq!(tool["outputSchema"]["type"], "object");
+        }
     }
 
     #[tokio::test]
     async fn call_tool_returns_corrections_array() {
-        let reg = crate::tools::registry::build_registry();
+        let reg = build_registry(&Config::default(), None);
         let out = super::call_tool(
             &reg,
             &serde_json::json!({
@@ -175,11 +246,62 @@ mod tests {
 
     #[tokio::test]
     async fn call_tool_errors_on_missing_name() {
-        let reg = crate::tools::registry::build_registry();
+        let reg = build_registry(&Config::default(), None);
         let err = super::call_tool(&reg, &serde_json::json!({}))
             .await
             .unwrap_err();
-        assert!(err.contains("missing tool name"));
+        assert!(err.to_string().contains("missing tool name"));
+        assert!(matches!(err, GatewayError::InvalidParams(_)));
+    }
+
+    #[tokio::test]
+    async fn http_tools_call_upstream_outage_returns_503_with_retry_after() {
+        use crate::core::tool::{Tool, ToolSpec};
+        use std::sync::Arc;
+
+        struct Down;
+        impl ToolSpec for Down {
+            fn name(&self) -> &'static str {
+                "down"
+            }
+            fn description(&self) -> &'static str {
+                "always unavailable"
+            }
+            fn input_schema(&self) -> J {
+                json!({"type":"object"})
+            }
+        }
+        #[async_trait::async_trait]
+        impl Tool for Down {
+            async fn call(&self, _: &J) -> Result<J, GatewayError> {
+                Err(GatewayError::UpstreamUnavailable {
+                    upstream: "http://gramadoir".into(),
+                    reason: "circuit open".into(),
+                    retry_after_secs: Some(7),
+                })
+            }
+        }
+
+        let mut tools = (*build_registry(&Config::default(), None).0).clone();
+        tools.insert("down", Arc::new(Down));
+        let reg = Registry(Arc::new(tools));
+        let app = Router::new()
+            .route("/mcp", post(super::http))
+            .with_state(reg);
+        let body = r#"{"jsonrpc":"2.0","id":6,"method":"tools.call","params":{"name":"down","arguments":{}}}"#;
+        let req = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers()[header::RETRY_AFTER], "7");
+        let bytes = to_bytes(resp.into_body(), BODY_LIMIT).await.unwrap();
+        let v: J = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(v["error"]["code"], -32001);
+        assert_eq!(v["error"]["data"]["retry_after_secs"], 7);
     }
 
     #[tokio::test]
@@ -217,8 +339,54 @@ mod tests {
         assert_eq!(v["result"]["corrections"], serde_json::Value::Array(vec![]));
     }
 
+    #[derive(Clone, Default)]
+    struct Captured(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn http_logs_keep_user_text_out_by_default() {
+        let logs = Captured::default();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_max_level(tracing::Level::TRACE)
+            .with_writer({
+                let logs = logs.clone();
+                move || logs.clone()
+            })
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let app = router_with_state();
+        let body = r#"{"jsonrpc":"2.0","id":2,"method":"tools.call","params":{"name":"gael.spellcheck.v1","arguments":{"text":"rúnda príobháideach"}}}"#;
+        let req = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        assert!(app.oneshot(req).await.unwrap().status().is_success());
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(logs.contains("tools.call ok response"), "{logs}");
+        assert!(logs.contains("[redacted 19 chars]"), "{logs}");
+        assert!(!logs.contains("rúnda"), "{logs}");
+        for line in logs.lines() {
+            serde_json::from_str::<J>(line).expect("one JSON object per line");
+        }
+    }
+
     #[tokio::test]
-    async fn http_tools_call_missing_arguments_returns_tool_error() {
+    async fn http_tools_call_missing_arguments_returns_invalid_params() {
         let app = router_with_state();
         let body = r#"{"jsonrpc":"2.0","id":5,"method":"tools.call","params":{"name":"gael.spellcheck.v1"}}"#;
         let req = Request::builder()
@@ -228,9 +396,11 @@ mod tests {
             .body(Body::from(body))
             .unwrap();
         let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
         let bytes = to_bytes(resp.into_body(), BODY_LIMIT).await.unwrap();
         let v: J = serde_json::from_slice(&bytes).unwrap();
-        assert_eq!(v["error"]["code"], -32000);
+        assert_eq!(v["error"]["code"], -32602);
+        assert_eq!(v["error"]["data"]["kind"], "invalid_params");
     }
 
     #[tokio::test]
@@ -244,9 +414,10 @@ mod tests {
             .body(Body::from(body))
             .unwrap();
         let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
         let bytes = to_bytes(resp.into_body(), BODY_LIMIT).await.unwrap();
         let v: J = serde_json::from_slice(&bytes).unwrap();
-        assert_eq!(v["error"]["code"], -32000);
+        assert_eq!(v["error"]["code"], -32002);
     }
 
     #[tokio::test]
@@ -280,7 +451,7 @@ mod tests {
 
     #[tokio::test]
     async fn handle_stdio_line_covers_initialize_and_list() {
-        let reg = crate::tools::registry::build_registry();
+        let reg = build_registry(&Config::default(), None);
         let init = super::handle_stdio_line(
             &reg,
             "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"initialize\"}",
@@ -297,7 +468,7 @@ mod tests {
 
     #[tokio::test]
     async fn handle_stdio_line_covers_unknown_and_parse_error() {
-        let reg = crate::tools::registry::build_registry();
+        let reg = build_registry(&Config::default(), None);
         let unk =
             super::handle_stdio_line(&reg, "{\"jsonrpc\":\"2.0\",\"id\":3,\"method\":\"nope\"}")
                 .await;
@@ -374,7 +545,7 @@ mod tests {
             }]));
         });
 
-        let reg = crate::tools::registry::build_registry();
+        let reg = build_registry(&Config::default(), None);
 
         let app = axum::Router::new()
             .route("/mcp", axum::routing::post(super::http))
@@ -1,13 +1,21 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
+use crate::infra::config::{Config, ConfigProblem};
+
 #[derive(Parser)]
 #[command(name = "irish-mcp-gateway")]
 #[command(about = "Irish MCP Gateway - Admin CLI")]
 #[command(version)]
 pub struct Cli {
+    /// TOML config file; environment variables override its values
+    #[arg(long, global = true, env = "CONFIG_PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Admin command; without one the gateway starts
     #[command(subcommand)]
-    pub command: Commands,
+    pub command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
@@ -23,6 +31,9 @@ pub enum Commands {
         /// Validate config without starting service
         #[arg(long)]
         validate: bool,
+        /// Report format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
     },
     /// Show service status and metrics
     Status {
@@ -41,13 +52,28 @@ pub enum Commands {
     },
 }
 
-pub async fn run() -> ExitCode {
-    let cli = Cli::parse();
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
 
-    run_commands(cli.command).await
+/// Load the config and run `command`. `config --validate` reports a config
+/// that fails to load as a problem; other commands give up.
+pub async fn run(command: Commands, config_path: Option<&Path>) -> ExitCode {
+    match (Config::load(config_path), command) {
+        (Ok(cfg), command) => run_commands(command, &cfg).await,
+        (Err(e), Commands::Config { validate, format }) => {
+            report_config(validate, format, vec![ConfigProblem::from(&e)])
+        }
+        (Err(e), _) => {
+            eprintln!("❌ {e}");
+            ExitCode::FAILURE
+        }
+    }
 }
 
-pub async fn run_commands(command: Commands) -> ExitCode {
+pub async fn run_commands(command: Commands, cfg: &Config) -> ExitCode {
     match command {
         Commands::Health { url } => match health_check(&url).await {
             Ok(_) => {
@@ -59,24 +85,15 @@ pub async fn run_commands(command: Commands) -> ExitCode {
                 ExitCode::FAILURE
             }
         },
-        Commands::Config { validate: _ } => match validate_config() {
-            Ok(_) => {
-                println!("✅ Configuration is valid");
-                ExitCode::SUCCESS
-            }
-            Err(e) => {
-                eprintln!("❌ Configuration validation failed: {}", e);
-                ExitCode::FAILURE
-            }
-        },
-        Commands::Status { url } => match show_status(&url).await {
+        Commands::Config { validate, format } => report_config(validate, format, cfg.validate()),
+        Commands::Status { url } => match show_status(&url, cfg).await {
             Ok(_) => ExitCode::SUCCESS,
             Err(e) => {
                 eprintln!("❌ Status check failed: {}", e);
                 ExitCode::FAILURE
             }
         },
-        Commands::TestGrammar { url, text } => match test_grammar(url, &text).await {
+        Commands::TestGrammar { url, text } => match test_grammar(url, &text, cfg).await {
             Ok(_) => {
                 println!("✅ Grammar service test passed");
                 ExitCode::SUCCESS
@@ -104,30 +121,39 @@ async fn health_check(url: &str) -> Result<(), Box<dyn std::error::Error>> {
     }
 }
 
-fn validate_config() -> Result<(), Box<dyn std::error::Error>> {
-    let _config = crate::infra::config::Config::from_env();
-
-    // Validate required environment variables
-    let mode = std::env::var("MODE").unwrap_or_else(|_| "server".into());
-    if !matches!(mode.as_str(), "server" | "stdio") {
-        return Err(format!("Invalid MODE: {}. Must be 'server' or 'stdio'", mode).into());
+fn report_config(validate: bool, format: OutputFormat, problems: Vec<ConfigProblem>) -> ExitCode {
+    if !validate {
+        eprintln!("Nothing to do: pass --validate to check the configuration");
+        return ExitCode::FAILURE;
     }
-
-    if mode == "server" {
-        let port = std::env::var("PORT")
-            .ok()
-            .and_then(|s| s.parse::<u16>().ok())
-            .unwrap_or(8080);
-
-        if port == 0 {
-            return Err("PORT cannot be 0".into());
+    let code = if problems.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    };
+    match format {
+        OutputFormat::Json => {
+            let report = serde_json::json!({ "valid": problems.is_empty(), "problems": problems });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).unwrap_or_default()
+            );
+        }
+        OutputFormat::Text if problems.is_empty() => println!("✅ Configuration is valid"),
+        OutputFormat::Text => {
+            eprintln!(
+                "❌ Configuration validation failed: {} problem(s)",
+                problems.len()
+            );
+            for problem in &problems {
+                eprintln!("  - {problem}");
+            }
         }
     }
-
-    Ok(())
+    code
 }
 
-async fn show_status(url: &str) -> Result<(), Box<dyn std::error::Error>> {
+async fn show_status(url: &str, cfg: &Config) -> Result<(), Box<dyn std::error::Error>> {
     let client = reqwest::Client::new();
 
     // Health check
@@ -174,20 +200,18 @@ async fn show_status(url: &str) -> Result<(), Box<dyn std::error::Error>> {
 
     // Configuration summary
     println!("\n📋 Configuration:");
+    println!("  Mode: {}", cfg.mode);
+    println!("  Port: {}", cfg.port);
     println!(
-        "  Mode: {}",
-        std::env::var("MODE").unwrap_or_else(|_| "server".into())
+        "  Log Level: {}",
+        std::env::var("RUST_LOG").unwrap_or_else(|_| cfg.logging.directives())
     );
     println!(
-        "  Port: {}",
-        std::env::var("PORT").unwrap_or_else(|_| "8080".into())
-    );
-    println!(
-        "  Log Level: {}",
-        std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into())
+        "  Log Format: {} (user text: {})",
+        cfg.logging.format, cfg.logging.user_text
     );
 
-    if let Ok(grammar_url) = std::env::var("GRAMADOIR_BASE_URL") {
+    if let Some(grammar_url) = &cfg.gramadoir.base_url {
         println!("  Grammar Service: {}", grammar_url);
     } else {
         println!("  Grammar Service: Not configured");
@@ -196,12 +220,17 @@ async fn show_status(url: &str) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-async fn test_grammar(url: Option<String>, text: &str) -> Result<(), Box<dyn std::error::Error>> {
+async fn test_grammar(
+    url: Option<String>,
+    text: &str,
+    cfg: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
     let grammar_url = url
-        .or_else(|| std::env::var("GRAMADOIR_BASE_URL").ok())
+        .or_else(|| cfg.gramadoir.base_url.clone())
         .ok_or("No grammar service URL provided")?;
 
-    let client = crate::clients::gramadoir::GramadoirRemote::new(grammar_url);
+    let client =
+        crate::clients::gramadoir::GramadoirRemote::new(grammar_url).with_upstream(&cfg.gramadoir);
     let issues = client.analyze(text).await?;
 
     println!("📝 Grammar check for: \"{}\"", text);
@@ -252,8 +281,7 @@ mod tests {
         env::set_var("MODE", "server");
         env::set_var("PORT", "8080");
 
-        let result = validate_config();
-        assert!(result.is_ok());
+        assert!(Config::from_env().validate().is_empty());
 
         env::remove_var("MODE");
         env::remove_var("PORT");
@@ -264,9 +292,9 @@ mod tests {
     fn test_validate_config_invalid_mode() {
         env::set_var("MODE", "invalid");
 
-        let result = validate_config();
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Invalid MODE"));
+        let problems = Config::from_env().validate();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].to_string().contains("Invalid MODE"));
 
         env::remove_var("MODE");
     }
@@ -276,8 +304,7 @@ mod tests {
     fn test_validate_config_stdio_mode() {
         env::set_var("MODE", "stdio");
 
-        let result = validate_config();
-        assert!(result.is_ok());
+        assert!(Config::from_env().validate().is_empty());
 
         env::remove_var("MODE");
     }
@@ -288,9 +315,9 @@ mod tests {
         env::set_var("MODE", "server");
         env::set_var("PORT", "0");
 
-        let result = validate_config();
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("PORT cannot be 0"));
+        let problems = Config::from_env().validate();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].to_string().contains("PORT cannot be 0"));
 
         env::remove_var("MODE");
         env::remove_var("PORT");
@@ -298,12 +325,16 @@ mod tests {
 
     #[test]
     #[serial]
-    fn validate_config_non_numeric_port_defaults() {
+    fn validate_config_reports_non_numeric_port() {
         env::set_var("MODE", "server");
         env::set_var("PORT", "abc");
 
-        let result = validate_config();
-        assert!(result.is_ok());
+        let cfg = Config::from_env();
+        assert_eq!(cfg.port, 8080);
+        let problems = cfg.validate();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].field, "PORT");
+        assert!(problems[0].message.contains("'abc'"), "{}", problems[0]);
 
         env::remove_var("MODE");
         env::remove_var("PORT");
@@ -322,16 +353,13 @@ mod tests {
             then.status(500).body("boom");
         });
 
-        let res = show_status(&server.base_url()).await;
+        let res = show_status(&server.base_url(), &Config::default()).await;
         assert!(res.is_ok());
     }
 
     #[tokio::test]
-    #[serial]
     async fn test_test_grammar_no_url() {
-        env::remove_var("GRAMADOIR_BASE_URL");
-
-        let result = test_grammar(None, "test").await;
+        let result = test_grammar(None, "test", &Config::default()).await;
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -342,58 +370,99 @@ mod tests {
     #[tokio::test]
     async fn test_test_grammar_with_url() {
         // This would need a real grammar service; expect an error
-        let result = test_grammar(Some("http://localhost:9999".to_string()), "test").await;
+        let result = test_grammar(
+            Some("http://localhost:9999".to_string()),
+            "test",
+            &Config::default(),
+        )
+        .await;
         assert!(result.is_err());
     }
 
     #[tokio::test]
     async fn test_status_handles_unavailable_service() {
         // Should handle errors and return Err when service is down
-        let res = show_status("http://localhost:9999").await;
+        let res = show_status("http://localhost:9999", &Config::default()).await;
         assert!(res.is_err());
     }
 
     #[tokio::test]
     #[serial]
     async fn run_commands_config_success() {
-        let code = run_commands(Commands::Config { validate: true }).await;
-        assert_eq!(code, ExitCode::SUCCESS);
+        for format in [OutputFormat::Text, OutputFormat::Json] {
+            let validate = Commands::Config {
+                validate: true,
+                format,
+            };
+            let code = run_commands(validate, &Config::default()).await;
+            assert_eq!(code, ExitCode::SUCCESS);
+        }
     }
 
     #[tokio::test]
     #[serial]
     async fn run_commands_config_failure() {
         env::set_var("MODE", "nope");
-        let code = run_commands(Commands::Config { validate: true }).await;
+        let validate = Commands::Config {
+            validate: true,
+            format: OutputFormat::Json,
+        };
+        let code = run_commands(validate, &Config::from_env()).await;
         assert_eq!(code, ExitCode::FAILURE);
         env::remove_var("MODE");
     }
 
+    #[tokio::test]
+    async fn run_reports_unloadable_config_file_for_validate() {
+        let missing = Path::new("/definitely/not/here/gateway.toml");
+        let validate = Commands::Config {
+            validate: true,
+            format: OutputFormat::Text,
+        };
+        assert_eq!(run(validate, Some(missing)).await, ExitCode::FAILURE);
+        let without_flag = Commands::Config {
+            validate: false,
+            format: OutputFormat::Text,
+        };
+        assert_eq!(
+            run_commands(without_flag, &Config::default()).await,
+            ExitCode::FAILURE
+        );
+    }
+
     #[tokio::test]
     #[serial]
     async fn run_commands_health_and_status() {
         // Health should fail against invalid URL and still return FAILURE
-        let health = run_commands(Commands::Health {
-            url: "http://localhost:9".into(),
-        })
+        let cfg = Config::default();
+        let health = run_commands(
+            Commands::Health {
+                url: "http://localhost:9".into(),
+            },
+            &cfg,
+        )
         .await;
         assert_eq!(health, ExitCode::FAILURE);
 
-        let status = run_commands(Commands::Status {
-            url: "http://localhost:9".into(),
-        })
+        let status = run_commands(
+            Commands::Status {
+                url: "http://localhost:9".into(),
+            },
+            &cfg,
+        )
         .await;
         assert_eq!(status, ExitCode::FAILURE);
     }
 
     #[tokio::test]
-    #[serial]
     async fn run_commands_test_grammar_no_url() {
-        env::remove_var("GRAMADOIR_BASE_URL");
-        let code = run_commands(Commands::TestGrammar {
-            url: None,
-            text: "abc".into(),
-        })
+        let code = run_commands(
+            Commands::TestGrammar {
+                url: None,
+                text: "abc".into(),
+            },
+            &Config::default(),
+        )
         .await;
         assert_eq!(code, ExitCode::FAILURE);
     }
@@ -428,7 +497,7 @@ mod tests {
             when.method(POST).path("/mcp");
             then.status(200).body("ok");
         });
-        let res = super::show_status(&server.base_url()).await;
+        let res = super::show_status(&server.base_url(), &Config::default()).await;
         assert!(res.is_ok());
     }
 
@@ -440,9 +509,12 @@ mod tests {
             when.method(GET).path("/healthz");
             then.status(200).body("ok");
         });
-        let code = run_commands(Commands::Health {
-            url: server.base_url(),
-        })
+        let code = run_commands(
+            Commands::Health {
+                url: server.base_url(),
+            },
+            &Config::default(),
+        )
         .await;
         assert_eq!(code, ExitCode::SUCCESS);
     }
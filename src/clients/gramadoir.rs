@@ -1,25 +1,78 @@
+use std::sync::Arc;
+
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::Instrument;
 
+use crate::core::error::GatewayError;
 use crate::domain::GrammarIssue;
+use crate::infra::config::UpstreamConfig;
 use crate::infra::http::headers::{add_standard_headers, generate_request_id};
-use crate::infra::runtime::limits::{make_http_client, retry_async};
+use crate::infra::metrics::{self, metrics};
+use crate::infra::runtime::breaker::CircuitBreaker;
+use crate::infra::runtime::cache::ResultCache;
+use crate::infra::runtime::limits::{
+    make_http_client, make_upstream_client, retry_if, AttemptError, RetryPolicy,
+};
+use crate::infra::runtime::queue::UpstreamQueue;
+use crate::infra::telemetry::{self, current_request_id};
+
+mod chunking;
+mod suggestions;
+
+/// Documents longer than this (in chars) are split into sentence-aligned chunks.
+pub const DEFAULT_CHUNK_CHARS: usize = 2000;
+/// Chunks of one document analysed at the same time.
+const CHUNK_CONCURRENCY: usize = 4;
 
 #[derive(Clone)]
 pub struct GramadoirRemote {
     base: String,
     http: Client,
+    chunk_chars: usize,
+    cache: Option<Arc<ResultCache>>,
+    breaker: Arc<CircuitBreaker>,
+    queue: Arc<UpstreamQueue>,
+    retry: RetryPolicy,
 }
 
 impl GramadoirRemote {
     pub fn new(base: impl Into<String>) -> Self {
         let http = make_http_client();
+        let base = base.into();
         Self {
-            base: base.into(),
+            breaker: CircuitBreaker::for_upstream(&base),
+            queue: UpstreamQueue::for_upstream(&base),
+            base,
             http,
+            chunk_chars: DEFAULT_CHUNK_CHARS,
+            cache: None,
+            retry: UpstreamConfig::default().retry_policy(),
         }
     }
 
+    /// Apply timeout, retry, pool, HTTP/2, circuit breaker, queue and chunking
+    /// settings.
+    pub fn with_upstream(mut self, cfg: &UpstreamConfig) -> Self {
+        self.http = make_upstream_client(cfg);
+        self.retry = cfg.retry_policy();
+        self.chunk_chars = cfg.chunk_chars;
+        self.breaker
+            .configure(cfg.breaker_failure_threshold, cfg.breaker_open_for);
+        self.queue
+            .configure(cfg.max_concurrent, cfg.max_queue, cfg.queue_timeout);
+        self
+    }
+
+    /// Cache raw issues per chunk of text. Rule filtering happens after the
+    /// cache, so differing rule options share entries.
+    pub fn with_cache(mut self, cache: Option<Arc<ResultCache>>) -> Self {
+        self.cache = cache;
+        self
+    }
+
     #[allow(dead_code)]
     pub async fn health(&self) -> bool {
         let url = format!("{}/health", self.base.trim_end_matches('/'));
@@ -30,15 +83,75 @@ impl GramadoirRemote {
         }
     }
 
-    pub async fn analyze(&self, text: &str) -> Result<Vec<GrammarIssue>, String> {
+    /// Analyse `text`, splitting long documents into sentence-aligned chunks that
+    /// are checked concurrently. Offsets always refer to `text`.
+    pub async fn analyze(&self, text: &str) -> Result<Vec<GrammarIssue>, GatewayError> {
+        let chunks = chunking::chunks(text, self.chunk_chars);
+        if chunks.len() <= 1 {
+            return self.analyze_chunk(text).await;
+        }
+        tracing::debug!(chunks = chunks.len(), "gramadoir.analyze chunked");
+
+        let limit = Arc::new(Semaphore::new(CHUNK_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+        for chunk in chunks {
+            let this = self.clone();
+            let limit = limit.clone();
+            tasks.spawn(telemetry::propagate(async move {
+                let _permit = limit.acquire_owned().await.map_err(|e| e.to_string())?;
+                let issues = this.analyze_chunk(&chunk.text).await?;
+                Ok::<_, GatewayError>((chunk.start, issues))
+            }));
+        }
+
+        let index = TextIndex::new(text);
+        let mut out = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            let (offset, issues) = joined.map_err(|e| GatewayError::Message(e.to_string()))??;
+            out.extend(issues.into_iter().map(|mut issue| {
+                issue.start += offset;
+                issue.end += offset;
+                issue.utf16_start = index.utf16(issue.start);
+                issue.utf16_end = index.utf16(issue.end);
+                issue
+            }));
+        }
+        out.sort_by_key(|i| (i.start, i.end));
+        Ok(out)
+    }
+
+    async fn analyze_chunk(&self, text: &str) -> Result<Vec<GrammarIssue>, GatewayError> {
+        let Some(cache) = &self.cache else {
+            return self.fetch(text).await;
+        };
+        let key = ResultCache::key("gramadoir", &[&self.base, text]);
+        if let Some(issues) = cache.get(&key).and_then(|v| serde_json::from_value(v).ok()) {
+            return Ok(issues);
+        }
+        let issues = self.fetch(text).await?;
+        if let Ok(value) = serde_json::to_value(&issues) {
+            cache.insert(key, value);
+        }
+        Ok(issues)
+    }
+
+    async fn fetch(&self, text: &str) -> Result<Vec<GrammarIssue>, GatewayError> {
         // TODO(refactor-fit-and-finish): Once we centralize ToolBackend HTTP clients,
-        // thread a shared client and request-id middleware through this path.
+        // thread a shared client through this path.
         let url = format!("{}/api/gramadoir/1.0", self.base.trim_end_matches('/'));
         let http = self.http.clone();
         let url_clone = url.clone();
         tracing::debug!(endpoint = %url, "gramadoir.analyze request");
-        let req_id = generate_request_id();
-        let issues: Vec<IssueWire> = retry_async(2, move |_| {
+        // Retries share the inbound request's id so the upstream can correlate them
+        let req_id = current_request_id().unwrap_or_else(generate_request_id);
+        let span = tracing::info_span!(
+            "upstream.call",
+            otel.kind = "client",
+            upstream = "gramadoir",
+            url.full = %url,
+            request_id = %req_id,
+        );
+        let attempt = retry_if(self.retry, AttemptError::is_unavailable, move |_| {
             let http = http.clone();
             let url = url_clone.clone();
             let req_id = req_id.clone();
@@ -49,21 +162,42 @@ impl GramadoirRemote {
                     .json(&payload)
                     .send()
                     .await
-                    .map_err(|e| e.to_string())?;
+                    .map_err(AttemptError::from)?;
                 if !resp.status().is_success() {
                     if resp.status().is_server_error() {
-                        return Err(format!("retryable status {}", resp.status()));
+                        return Err(AttemptError::Unavailable(format!(
+                            "retryable status {}",
+                            resp.status()
+                        )));
                     }
-                    return Err(format!("upstream status {}", resp.status()));
+                    return Err(AttemptError::BadResponse(format!(
+                        "upstream status {}",
+                        resp.status()
+                    )));
                 }
                 resp.json::<Vec<IssueWire>>()
                     .await
-                    .map_err(|e| e.to_string())
+                    .map_err(AttemptError::from)
             }
-        })
-        .await?;
+        });
+        // Queue only once the breaker admits the call, so callers fail fast
+        // while it is open. Retries keep their slot, so a struggling upstream
+        // never sees more than `max_concurrent` requests from us
+        let permit = self.breaker.acquire();
+        let _slot = match permit {
+            Ok(_) => Some(self.queue.acquire().await?),
+            Err(_) => None,
+        };
+        let started = std::time::Instant::now();
+        let result = async { permit?.run(attempt).await }.instrument(span).await;
+        metrics().observe_upstream("gramadoir", metrics::outcome(&result), started.elapsed());
+        let issues: Vec<IssueWire> = result?;
 
-        Ok(issues.into_iter().map(GrammarIssue::from).collect())
+        let index = TextIndex::new(text);
+        Ok(issues
+            .into_iter()
+            .map(|issue| issue.into_issue(&index))
+            .collect())
     }
 }
 
@@ -93,32 +227,154 @@ struct IssueWire {
     tox: String,
     #[serde(default)]
     toy: String,
+    // Not emitted by stock Gramadóir; honoured when a server provides them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    suggestions: Vec<String>,
 }
 
-impl From<IssueWire> for GrammarIssue {
-    fn from(w: IssueWire) -> Self {
-        fn parse_usize(s: &str) -> usize {
-            s.parse::<usize>().unwrap_or(0)
-        }
-        let start = parse_usize(&w.fromx);
-        let end = {
-            let tox = parse_usize(&w.tox);
-            if tox > 0 {
-                tox
-            } else {
-                start + parse_usize(&w.errorlength)
+impl IssueWire {
+    /// The text this issue covers, taken from `context` at `contextoffset`/`errorlength`.
+    fn flagged(&self) -> Option<String> {
+        let context = self.context.as_deref()?;
+        let offset = self.contextoffset.parse::<usize>().ok()?;
+        let len = self.errorlength.parse::<usize>().ok()?;
+        let flagged: String = context.chars().skip(offset).take(len).collect();
+        (!flagged.is_empty()).then_some(flagged)
+    }
+}
+
+impl IssueWire {
+    /// Map into a `GrammarIssue` with absolute offsets into the analysed text.
+    ///
+    /// Gramadóir reports `fromy`/`fromx` as line/column and `toy`/`tox` as an
+    /// inclusive end. When the resulting span does not cover the flagged text
+    /// (e.g. the server counts lines differently), fall back to locating
+    /// `context` in the text and applying `contextoffset`.
+    fn into_issue(self, index: &TextIndex) -> GrammarIssue {
+        fn parse(s: &str) -> Option<usize> {
+            s.parse::<usize>().ok()
+        }
+        let mut start = index.position(
+            parse(&self.fromy).unwrap_or(0),
+            parse(&self.fromx).unwrap_or(0),
+        );
+        let mut end = match (parse(&self.errorlength), parse(&self.toy), parse(&self.tox)) {
+            (Some(len), _, _) if len > 0 => start + len,
+            (_, Some(line), Some(col)) => index.position(line, col) + 1,
+            _ => start,
+        };
+
+        let flagged = self.flagged();
+        if let Some(flagged) = flagged.as_deref() {
+            if !index.covers(start, flagged) {
+                let relocated = self
+                    .context
+                    .as_deref()
+                    .zip(parse(&self.contextoffset))
+                    .and_then(|(ctx, off)| {
+                        index
+                            .find_near(ctx, start.saturating_sub(off))
+                            .map(|p| p + off)
+                    })
+                    .filter(|p| index.covers(*p, flagged))
+                    .or_else(|| index.find_near(flagged, start));
+                if let Some(pos) = relocated {
+                    start = pos;
+                    end = pos + flagged.chars().count();
+                }
             }
+        }
+        let end = end.min(index.len()).max(start.min(index.len()));
+        let start = start.min(end);
+
+        let suggestions = if self.suggestions.is_empty() {
+            suggestions::from_message(&self.msg, flagged.as_deref())
+        } else {
+            self.suggestions
         };
         GrammarIssue {
-            code: w.rule_id,
-            message: w.msg,
+            code: self.rule_id,
+            message: self.msg,
             start,
             end,
-            suggestions: Vec::new(),
+            utf16_start: index.utf16(start),
+            utf16_end: index.utf16(end),
+            suggestions,
         }
     }
 }
 
+/// Char-level view of the analysed text for mapping line/column positions.
+struct TextIndex {
+    chars: Vec<char>,
+    /// Char offset at which each line starts.
+    line_starts: Vec<usize>,
+    /// UTF-16 offset of each char boundary (`chars.len() + 1` entries).
+    utf16: Vec<usize>,
+}
+
+impl TextIndex {
+    fn new(text: &str) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let mut line_starts = vec![0];
+        let mut utf16 = Vec::with_capacity(chars.len() + 1);
+        let mut u = 0;
+        for (i, c) in chars.iter().enumerate() {
+            utf16.push(u);
+            u += c.len_utf16();
+            if *c == '\n' {
+                line_starts.push(i + 1);
+            }
+        }
+        utf16.push(u);
+        Self {
+            chars,
+            line_starts,
+            utf16,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    /// Absolute char offset of `line`/`col`, clamped to the text.
+    fn position(&self, line: usize, col: usize) -> usize {
+        let line_start = self
+            .line_starts
+            .get(line)
+            .copied()
+            .unwrap_or_else(|| *self.line_starts.last().unwrap_or(&0));
+        (line_start + col).min(self.len())
+    }
+
+    fn utf16(&self, char_idx: usize) -> usize {
+        self.utf16[char_idx.min(self.len())]
+    }
+
+    fn covers(&self, start: usize, needle: &str) -> bool {
+        let mut at = start;
+        for c in needle.chars() {
+            if self.chars.get(at) != Some(&c) {
+                return false;
+            }
+            at += 1;
+        }
+        true
+    }
+
+    /// Occurrence of `needle` whose start is closest to `near`.
+    fn find_near(&self, needle: &str, near: usize) -> Option<usize> {
+        let n = needle.chars().count();
+        if n == 0 || n > self.len() {
+            return None;
+        }
+        (0..=self.len() - n)
+            .filter(|&i| self.covers(i, needle))
+            .min_by_key(|&i| i.abs_diff(near))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,6 +402,7 @@ mod tests {
                 rule_id: "Lingua::GA::Gramadoir/CLAOCHLU".into(),
                 tox: "21".into(),
                 toy: "0".into(),
+                suggestions: Vec::new(),
             }]));
         });
 
@@ -157,7 +414,167 @@ mod tests {
         assert_eq!(out[0].code, "Lingua::GA::Gramadoir/CLAOCHLU");
         assert_eq!(out[0].message, "Initial mutation missing");
         assert_eq!(out[0].start, 12);
-        assert_eq!(out[0].end, 21);
+        assert_eq!(out[0].end, 22);
+    }
+
+    #[test]
+    fn it_parses_suggestions_from_message_or_structured_field() {
+        let wire: IssueWire = serde_json::from_value(json!({
+            "context":"Tá an peann ar an bord","contextoffset":"12","errorlength":"10",
+            "fromx":"12","fromy":"0","msg":"Eclipsis missing",
+            "ruleId":"Lingua::GA::Gramadoir/CLAOCHLU","tox":"21","toy":"0"
+        }))
+        .unwrap();
+        let index = TextIndex::new("Tá an peann ar an bord");
+        assert_eq!(wire.into_issue(&index).suggestions, vec!["ar an mbord"]);
+
+        let wire: IssueWire = serde_json::from_value(json!({
+            "msg":"Ba chóir duit /cóta/ a úsáid","ruleId":"CAIGHDEAN"
+        }))
+        .unwrap();
+        assert_eq!(wire.into_issue(&index).suggestions, vec!["cóta"]);
+
+        let wire: IssueWire = serde_json::from_value(json!({
+            "msg":"Ba chóir duit /cóta/ a úsáid","ruleId":"CAIGHDEAN",
+            "suggestions":["cótaí"]
+        }))
+        .unwrap();
+        assert_eq!(wire.into_issue(&index).suggestions, vec!["cótaí"]);
+    }
+
+    #[test]
+    fn it_computes_absolute_offsets_across_lines() {
+        let text = "Dia duit.\nTá an peann ar an bord 😀 anseo.";
+        let index = TextIndex::new(text);
+        let wire: IssueWire = serde_json::from_value(json!({
+            "context":"Tá an peann ar an bord 😀 anseo.","contextoffset":"12","errorlength":"10",
+            "fromx":"12","fromy":"1","toy":"1","tox":"21",
+            "msg":"Eclipsis missing","ruleId":"CLAOCHLU"
+        }))
+        .unwrap();
+        let issue = wire.into_issue(&index);
+        assert_eq!(issue.start, 22);
+        assert_eq!(issue.end, 32);
+        let flagged: String = text.chars().skip(issue.start).take(10).collect();
+        assert_eq!(flagged, "ar an bord");
+
+        // The emoji is two UTF-16 code units, shifting later offsets by one.
+        let wire: IssueWire = serde_json::from_value(json!({
+            "context":"Tá an peann ar an bord 😀 anseo.","contextoffset":"25","errorlength":"5",
+            "fromx":"25","fromy":"1","toy":"1","tox":"29",
+            "msg":"Unknown word","ruleId":"ANAITHNID"
+        }))
+        .unwrap();
+        let issue = wire.into_issue(&index);
+        assert_eq!((issue.start, issue.end), (35, 40));
+        assert_eq!((issue.utf16_start, issue.utf16_end), (36, 41));
+    }
+
+    #[test]
+    fn it_relocates_via_context_when_line_numbers_disagree() {
+        let text = "Dia duit.\nTá an peann ar an bord.\nSlán.";
+        let index = TextIndex::new(text);
+        // 1-based line numbers point at "Slán.", so fall back to context.
+        let wire: IssueWire = serde_json::from_value(json!({
+            "context":"Tá an peann ar an bord.","contextoffset":"12","errorlength":"10",
+            "fromx":"12","fromy":"2","toy":"2","tox":"21",
+            "msg":"Eclipsis missing","ruleId":"CLAOCHLU"
+        }))
+        .unwrap();
+        let issue = wire.into_issue(&index);
+        assert_eq!((issue.start, issue.end), (22, 32));
+    }
+
+    #[test]
+    fn it_uses_inclusive_tox_when_errorlength_missing() {
+        let index = TextIndex::new("ab\ncd ef");
+        let wire: IssueWire = serde_json::from_value(json!({
+            "fromx":"0","fromy":"1","toy":"1","tox":"1","msg":"x","ruleId":"R"
+        }))
+        .unwrap();
+        let issue = wire.into_issue(&index);
+        assert_eq!((issue.start, issue.end), (3, 5));
+    }
+
+    fn chunked(chunk_chars: usize) -> UpstreamConfig {
+        UpstreamConfig {
+            chunk_chars,
+            ..UpstreamConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn it_chunks_long_documents_and_remaps_offsets() {
+        let server = MockServer::start();
+        let first = server.mock(|when, then| {
+            when.method(POST)
+                .path("/api/gramadoir/1.0")
+                .json_body(json!({"teacs":"Tá an peann ar an bord. "}));
+            then.status(200).json_body(json!([{
+                "context":"Tá an peann ar an bord.","contextoffset":"12","errorlength":"10",
+                "fromx":"12","fromy":"0","msg":"Eclipsis missing","ruleId":"CLAOCHLU"
+            }]));
+        });
+        let second = server.mock(|when, then| {
+            when.method(POST)
+                .path("/api/gramadoir/1.0")
+                .json_body(json!({"teacs":"😀 Srl. cota é."}));
+            then.status(200).json_body(json!([{
+                "context":"😀 Srl. cota é.","contextoffset":"7","errorlength":"4",
+                "fromx":"7","fromy":"0","msg":"Unknown word","ruleId":"ANAITHNID"
+            }]));
+        });
+
+        let text = "Tá an peann ar an bord. 😀 Srl. cota é.";
+        let cli = GramadoirRemote::new(server.base_url()).with_upstream(&chunked(25));
+        let out = cli.analyze(text).await.unwrap();
+        first.assert();
+        second.assert();
+
+        assert_eq!(out.len(), 2);
+        assert_eq!((out[0].start, out[0].end), (12, 22));
+        assert_eq!(out[1].code, "ANAITHNID");
+        assert_eq!((out[1].start, out[1].end), (31, 35));
+        let flagged: String = text.chars().skip(31).take(4).collect();
+        assert_eq!(flagged, "cota");
+        // The emoji in the second chunk shifts UTF-16 offsets by one.
+        assert_eq!((out[1].utf16_start, out[1].utf16_end), (32, 36));
+    }
+
+    #[tokio::test]
+    async fn it_fails_when_any_chunk_fails() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/api/gramadoir/1.0");
+            then.status(400).body("bad");
+        });
+        let cli = GramadoirRemote::new(server.base_url()).with_upstream(&chunked(5));
+        let err = cli.analyze("Aon. Dó. Trí.").await.unwrap_err();
+        assert!(err.to_string().contains("upstream status"));
+    }
+
+    #[tokio::test]
+    async fn it_reuses_cached_chunks() {
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(POST).path("/api/gramadoir/1.0");
+            then.status(200).json_body(json!([{
+                "fromx":"0","fromy":"0","errorlength":"3","msg":"x","ruleId":"R"
+            }]));
+        });
+        let cache = Arc::new(ResultCache::new(8, std::time::Duration::from_secs(60)));
+        let cli = GramadoirRemote::new(server.base_url())
+            .with_upstream(&chunked(10))
+            .with_cache(Some(cache.clone()));
+
+        let out = cli.analyze("Aon a dó. Trí.").await.unwrap();
+        assert_eq!(out.len(), 2);
+        assert_eq!((out[1].start, out[1].end), (10, 13));
+        // The second pass is served entirely from the cache.
+        let again = cli.analyze("Aon a dó. Trí.").await.unwrap();
+        assert_eq!((again[1].start, again[1].end), (10, 13));
+        m.assert_hits(2);
+        assert_eq!(cache.stats().hits, 2);
     }
 
     #[tokio::test]
@@ -190,7 +607,138 @@ mod tests {
         });
         let cli = GramadoirRemote::new(server.base_url());
         let err = cli.analyze("x").await.unwrap_err();
-        assert!(err.contains("upstream status"));
+        assert!(err.to_string().contains("upstream status"));
+    }
+
+    #[tokio::test]
+    async fn it_fails_fast_once_the_breaker_opens() {
+        // Nothing listens on port 9; every attempt is a connect error.
+        let cli = GramadoirRemote::new("http://127.0.0.1:9");
+        for _ in 0..5 {
+            let err = cli.analyze("x").await.unwrap_err();
+            assert!(matches!(
+                err,
+                GatewayError::UpstreamUnavailable {
+                    retry_after_secs: None,
+                    ..
+                }
+            ));
+        }
+        let started = std::time::Instant::now();
+        let err = cli.analyze("x").await.unwrap_err();
+        assert!(matches!(
+            err,
+            GatewayError::UpstreamUnavailable {
+                retry_after_secs: Some(_),
+                ..
+            }
+        ));
+        assert!(started.elapsed() < std::time::Duration::from_millis(50));
+        let snapshot = CircuitBreaker::for_upstream("http://127.0.0.1:9").snapshot();
+        assert_eq!(
+            snapshot.state,
+            crate::infra::runtime::breaker::BreakerState::Open
+        );
+    }
+
+    #[tokio::test]
+    async fn an_open_breaker_fails_fast_without_queueing() {
+        let url = "http://breaker-before-queue.invalid";
+        let cfg = UpstreamConfig {
+            breaker_failure_threshold: 1,
+            breaker_open_for: std::time::Duration::from_secs(60),
+            max_concurrent: 1,
+            queue_timeout: std::time::Duration::from_secs(5),
+            ..UpstreamConfig::default()
+        };
+        let cli = GramadoirRemote::new(url).with_upstream(&cfg);
+        let _ = CircuitBreaker::for_upstream(url)
+            .acquire()
+            .unwrap()
+            .run(async { Err::<(), _>(AttemptError::Unavailable("connect refused".into())) })
+            .await;
+        // With the only slot taken, a queued call would wait for `queue_timeout`.
+        let _busy = UpstreamQueue::for_upstream(url).acquire().await.unwrap();
+
+        let started = std::time::Instant::now();
+        let err = cli.analyze("x").await.unwrap_err();
+        assert!(err.to_string().contains("circuit open"), "{err}");
+        assert!(started.elapsed() < std::time::Duration::from_millis(50));
+        assert_eq!(UpstreamQueue::for_upstream(url).snapshot().queued, 0);
+    }
+
+    #[tokio::test]
+    async fn it_sheds_load_beyond_the_upstream_queue() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/api/gramadoir/1.0");
+            then.status(200)
+                .delay(std::time::Duration::from_millis(300))
+                .json_body(json!([]));
+        });
+        let cfg = UpstreamConfig {
+            max_concurrent: 1,
+            max_queue: 0,
+            ..UpstreamConfig::default()
+        };
+        let cli = GramadoirRemote::new(server.base_url()).with_upstream(&cfg);
+
+        let (first, second) = tokio::join!(cli.analyze("a"), async {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            cli.analyze("b").await
+        });
+        assert!(first.is_ok());
+        let err = second.unwrap_err();
+        assert!(matches!(err, GatewayError::UpstreamUnavailable { .. }));
+        assert!(err.to_string().contains("overloaded"), "{err}");
+        let queue = UpstreamQueue::for_upstream(&server.base_url()).snapshot();
+        assert_eq!((queue.in_flight, queue.queued), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn it_does_not_retry_client_errors_or_bad_json() {
+        let server = MockServer::start();
+        let bad_request = server.mock(|when, then| {
+            when.method(POST)
+                .path("/api/gramadoir/1.0")
+                .json_body(json!({"teacs":"4xx"}));
+            then.status(400).body("bad");
+        });
+        let bad_json = server.mock(|when, then| {
+            when.method(POST)
+                .path("/api/gramadoir/1.0")
+                .json_body(json!({"teacs":"json"}));
+            then.status(200).body("not json");
+        });
+        let cli = GramadoirRemote::new(server.base_url());
+        assert!(matches!(
+            cli.analyze("4xx").await,
+            Err(GatewayError::UpstreamBadResponse { .. })
+        ));
+        assert!(matches!(
+            cli.analyze("json").await,
+            Err(GatewayError::UpstreamBadResponse { .. })
+        ));
+        bad_request.assert_hits(1);
+        bad_json.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn it_retries_server_errors_up_to_the_configured_count() {
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(POST).path("/api/gramadoir/1.0");
+            then.status(503);
+        });
+        let upstream = UpstreamConfig {
+            retries: 3,
+            backoff_cap: std::time::Duration::from_millis(5),
+            ..UpstreamConfig::default()
+        };
+        let cli = GramadoirRemote::new(server.base_url()).with_upstream(&upstream);
+        let err = cli.analyze("x").await.unwrap_err();
+        assert!(err.to_string().contains("retryable status"));
+        m.assert_hits(4);
     }
 
     #[tokio::test]
@@ -0,0 +1,205 @@
+//! Irish-aware sentence splitting used to chunk long documents before they are
+//! sent to Gramadóir.
+
+/// Abbreviations that end in a full stop without ending the sentence, compared
+/// case-insensitively against the word before the stop.
+const ABBREVIATIONS: &[&str] = &[
+    "srl.", "m.sh.", "uimh.", "lch.", "lgh.", "fch.", "tchr.", "dr.", "uas.", "co.", "r.ch.",
+    ".i.", "e.g.", "i.e.", "etc.",
+];
+
+/// A slice of the input starting at char offset `start`.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Chunk {
+    pub start: usize,
+    pub text: String,
+}
+
+/// Char offsets at which sentences start. Each sentence runs up to the next start,
+/// so trailing whitespace belongs to the sentence before it.
+pub(crate) fn sentence_starts(chars: &[char]) -> Vec<usize> {
+    let mut starts = vec![0];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let paragraph = c == '\n' && chars.get(i + 1) == Some(&'\n');
+        if !paragraph && !matches!(c, '.' | '!' | '?' | '…') {
+            i += 1;
+            continue;
+        }
+        let mut j = i + 1;
+        while j < chars.len() && is_closing(chars[j]) {
+            j += 1;
+        }
+        let ends_sentence = paragraph
+            || (j < chars.len()
+                && chars[j].is_whitespace()
+                && !(c == '.' && j == i + 1 && is_abbreviation(&chars[..=i])));
+        if !ends_sentence {
+            i = j;
+            continue;
+        }
+        while j < chars.len() && chars[j].is_whitespace() {
+            j += 1;
+        }
+        if j < chars.len() {
+            starts.push(j);
+        }
+        i = j;
+    }
+    starts
+}
+
+fn is_closing(c: char) -> bool {
+    matches!(
+        c,
+        '.' | '!' | '?' | '…' | '"' | '\'' | '’' | '”' | '»' | ')' | ']'
+    )
+}
+
+/// True when the word ending at the final `.` of `before` is a known abbreviation
+/// or a single capital initial (`S. Ó Néill`).
+fn is_abbreviation(before: &[char]) -> bool {
+    let word_start = before
+        .iter()
+        .rposition(|c| c.is_whitespace())
+        .map_or(0, |p| p + 1);
+    let word: String = before[word_start..]
+        .iter()
+        .skip_while(|c| matches!(c, '(' | '"' | '\'' | '‘' | '“' | '«'))
+        .collect();
+    let mut letters = word.chars();
+    if let (Some(initial), Some('.'), None) = (letters.next(), letters.next(), letters.next()) {
+        if initial.is_uppercase() {
+            return true;
+        }
+    }
+    let lower = word.to_lowercase();
+    ABBREVIATIONS.contains(&lower.as_str())
+}
+
+/// Pack whole sentences into chunks of at most `max_chars` chars. A sentence longer
+/// than `max_chars` is split at the last whitespace that fits.
+pub(crate) fn chunks(text: &str, max_chars: usize) -> Vec<Chunk> {
+    let chars: Vec<char> = text.chars().collect();
+    let max_chars = max_chars.max(1);
+    if chars.len() <= max_chars {
+        return vec![Chunk {
+            start: 0,
+            text: text.to_string(),
+        }];
+    }
+
+    let mut bounds = sentence_starts(&chars);
+    bounds.push(chars.len());
+    let mut out = Vec::new();
+    let mut chunk_start = 0;
+    let mut push = |from: usize, to: usize| {
+        out.push(Chunk {
+            start: from,
+            text: chars[from..to].iter().collect(),
+        })
+    };
+    for pair in bounds.windows(2) {
+        let (sentence_start, sentence_end) = (pair[0], pair[1]);
+        if sentence_end - chunk_start > max_chars && sentence_start > chunk_start {
+            push(chunk_start, sentence_start);
+            chunk_start = sentence_start;
+        }
+        while sentence_end - chunk_start > max_chars {
+            let limit = chunk_start + max_chars;
+            let cut = (chunk_start + 1..=limit)
+                .rev()
+                .find(|&k| chars[k - 1].is_whitespace())
+                .unwrap_or(limit);
+            push(chunk_start, cut);
+            chunk_start = cut;
+        }
+    }
+    if chunk_start < chars.len() {
+        push(chunk_start, chars.len());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentences(text: &str) -> Vec<String> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut starts = sentence_starts(&chars);
+        starts.push(chars.len());
+        starts
+            .windows(2)
+            .map(|w| {
+                chars[w[0]..w[1]]
+                    .iter()
+                    .collect::<String>()
+                    .trim()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_splits_on_terminal_punctuation() {
+        assert_eq!(
+            sentences("Dia duit! Conas atá tú? Tá mé go maith… Go raibh maith agat."),
+            vec![
+                "Dia duit!",
+                "Conas atá tú?",
+                "Tá mé go maith…",
+                "Go raibh maith agat."
+            ]
+        );
+    }
+
+    #[test]
+    fn it_keeps_irish_abbreviations_and_initials_inside_sentences() {
+        assert_eq!(
+            sentences("Úlla, piorraí srl. a cheannach. Féach m.sh. Uimh. 5 ar lch. 3. Scríobh S. Ó Néill é."),
+            vec![
+                "Úlla, piorraí srl. a cheannach.",
+                "Féach m.sh. Uimh. 5 ar lch. 3.",
+                "Scríobh S. Ó Néill é."
+            ]
+        );
+    }
+
+    #[test]
+    fn it_splits_after_closing_quotes_and_paragraphs() {
+        assert_eq!(
+            sentences("Dúirt sé \"Slán.\" D'imigh sé\n\nCeannteideal"),
+            vec!["Dúirt sé \"Slán.\"", "D'imigh sé", "Ceannteideal"]
+        );
+    }
+
+    #[test]
+    fn chunks_pack_sentences_and_cover_the_input() {
+        let text = "Tá sé fuar. Tá sé fliuch. Tá sé dorcha.";
+        let out = chunks(text, 26);
+        assert_eq!(
+            out,
+            vec![
+                Chunk {
+                    start: 0,
+                    text: "Tá sé fuar. Tá sé fliuch. ".into()
+                },
+                Chunk {
+                    start: 26,
+                    text: "Tá sé dorcha.".into()
+                },
+            ]
+        );
+        assert_eq!(chunks(text, 100).len(), 1);
+    }
+
+    #[test]
+    fn chunks_split_oversized_sentences_at_whitespace() {
+        let out = chunks("aaa bbb ccc ddd", 8);
+        let texts: Vec<&str> = out.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, vec!["aaa bbb ", "ccc ddd"]);
+        assert_eq!(out[1].start, 8);
+    }
+}
@@ -0,0 +1,269 @@
+//! Recover replacement suggestions from Gramadóir messages.
+//!
+//! Gramadóir reports fixes in prose, in English or Irish depending on the
+//! server locale: either a quoted replacement (`Ba chóir duit /X/ a úsáid`,
+//! `Did you mean /X/?`) or a named initial mutation (`Lenition missing`,
+//! `Urú ar iarraidh`) that we apply to the flagged text ourselves.
+
+/// Phrases that introduce a `/replacement/` in a message.
+const REPLACEMENT_CUES: &[&str] = &[
+    "use",
+    "mean",
+    "form of",
+    "chóir duit",
+    "chóir",
+    "raibh",
+    "neamhchaighdeánach de",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mutation {
+    Lenite,
+    Eclipse,
+    Delenite,
+    Uneclipse,
+    PrefixH,
+    PrefixT,
+}
+
+const MUTATION_CUES: &[(&str, Mutation)] = &[
+    ("unnecessary lenition", Mutation::Delenite),
+    ("séimhiú gan ghá", Mutation::Delenite),
+    ("unnecessary eclipsis", Mutation::Uneclipse),
+    ("urú gan ghá", Mutation::Uneclipse),
+    ("lenition missing", Mutation::Lenite),
+    ("séimhiú ar iarraidh", Mutation::Lenite),
+    ("eclipsis missing", Mutation::Eclipse),
+    ("urú ar iarraidh", Mutation::Eclipse),
+    ("prefix /h/ missing", Mutation::PrefixH),
+    ("réamhlitir /h/ ar iarraidh", Mutation::PrefixH),
+    ("prefix /t/ missing", Mutation::PrefixT),
+    ("réamhlitir /t/ ar iarraidh", Mutation::PrefixT),
+];
+
+/// Suggested replacements for `flagged` (the text the issue covers), best first.
+pub(crate) fn from_message(msg: &str, flagged: Option<&str>) -> Vec<String> {
+    let mut out = quoted_replacements(msg);
+    if out.is_empty() {
+        if let Some(fixed) = flagged.and_then(|f| mutation_fix(msg, f)) {
+            out.push(fixed);
+        }
+    }
+    out.dedup();
+    out
+}
+
+fn quoted_replacements(msg: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = msg;
+    let mut consumed = 0;
+    while let Some(open) = rest.find('/') {
+        let after = &rest[open + 1..];
+        let Some(close) = after.find('/') else { break };
+        let lead = msg[..consumed + open].trim_end().to_lowercase();
+        let quoted = &after[..close];
+        if REPLACEMENT_CUES
+            .iter()
+            .any(|cue| ends_with_word(&lead, cue))
+        {
+            out.extend(
+                quoted
+                    .split(", ")
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(String::from),
+            );
+        }
+        let step = open + 1 + close + 1;
+        consumed += step;
+        rest = &rest[step..];
+    }
+    out
+}
+
+/// Whether `text` ends with `cue` as whole words, so `use` does not match
+/// `because`.
+fn ends_with_word(text: &str, cue: &str) -> bool {
+    text.strip_suffix(cue)
+        .is_some_and(|before| !before.ends_with(char::is_alphanumeric))
+}
+
+fn mutation_fix(msg: &str, flagged: &str) -> Option<String> {
+    let lower = msg.to_lowercase();
+    let (_, mutation) = MUTATION_CUES.iter().find(|(cue, _)| lower.contains(cue))?;
+    // The mutation applies to the last word of the flagged span (`ar an bord` -> `bord`).
+    let split = flagged.rfind(' ').map_or(0, |i| i + 1);
+    let (head, word) = flagged.split_at(split);
+    let fixed = apply(*mutation, word)?;
+    Some(format!("{head}{fixed}"))
+}
+
+const LENITABLE: &[char] = &['b', 'c', 'd', 'f', 'g', 'm', 'p', 's', 't'];
+
+fn is_vowel(c: char) -> bool {
+    "aeiouáéíóúAEIOUÁÉÍÓÚ".contains(c)
+}
+
+fn apply(mutation: Mutation, word: &str) -> Option<String> {
+    let mut chars = word.chars();
+    let first = chars.next()?;
+    let second = chars.next();
+    let lower = first.to_lowercase().next().unwrap_or(first);
+    match mutation {
+        Mutation::Lenite => {
+            if !LENITABLE.contains(&lower) || second.is_some_and(|c| c == 'h' || c == 'H') {
+                return None;
+            }
+            // sc, sm, sp, st never lenite
+            if lower == 's' && second.is_some_and(|c| "cmptCMPT".contains(c)) {
+                return None;
+            }
+            let h = if word.chars().all(|c| !c.is_lowercase()) {
+                'H'
+            } else {
+                'h'
+            };
+            Some(format!("{first}{h}{}", &word[first.len_utf8()..]))
+        }
+        Mutation::Delenite => {
+            if !LENITABLE.contains(&lower) || !second.is_some_and(|c| c == 'h' || c == 'H') {
+                return None;
+            }
+            Some(format!("{first}{}", &word[first.len_utf8() + 1..]))
+        }
+        Mutation::Eclipse => {
+            let prefix = match lower {
+                'b' => "m",
+                'c' => "g",
+                'd' | 'g' => "n",
+                'f' => "bh",
+                'p' => "b",
+                't' => "d",
+                v if is_vowel(v) => {
+                    return Some(if first.is_uppercase() {
+                        format!("n{word}")
+                    } else {
+                        format!("n-{word}")
+                    });
+                }
+                _ => return None,
+            };
+            Some(format!("{prefix}{word}"))
+        }
+        Mutation::Uneclipse => {
+            let lw = word.to_lowercase();
+            // (eclipsed onset, length of the eclipsing prefix)
+            let strip = [
+                ("bhf", 2),
+                ("mb", 1),
+                ("gc", 1),
+                ("nd", 1),
+                ("ng", 1),
+                ("bp", 1),
+                ("dt", 1),
+                ("n-", 2),
+            ]
+            .iter()
+            .find(|(onset, _)| lw.starts_with(onset) && lw.len() > onset.len())
+            .map(|(_, len)| *len)
+            .or_else(|| {
+                (lower == 'n' && second.is_some_and(|c| c.is_uppercase() && is_vowel(c)))
+                    .then_some(1)
+            })?;
+            Some(word[strip..].to_string())
+        }
+        Mutation::PrefixH => is_vowel(first).then(|| format!("h{word}")),
+        Mutation::PrefixT => {
+            if is_vowel(first) {
+                Some(if first.is_uppercase() {
+                    format!("t{word}")
+                } else {
+                    format!("t-{word}")
+                })
+            } else if lower == 's' {
+                Some(format!("t{word}"))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_quoted_replacements_in_both_locales() {
+        assert_eq!(
+            from_message("Ba chóir duit /an bhó/ a úsáid anseo", None),
+            vec!["an bhó"]
+        );
+        assert_eq!(
+            from_message("You should use /ar an mbord/ here instead", None),
+            vec!["ar an mbord"]
+        );
+        assert_eq!(
+            from_message("Do you mean /féin, fein/?", None),
+            vec!["féin", "fein"]
+        );
+        assert_eq!(
+            from_message("An raibh /cóta/ ar intinn agat?", None),
+            vec!["cóta"]
+        );
+        assert_eq!(
+            from_message("Non-standard form of /cúig/", None),
+            vec!["cúig"]
+        );
+    }
+
+    #[test]
+    fn ignores_quotes_that_are_not_replacements() {
+        assert!(from_message("Derived from the root /bord/", None).is_empty());
+        assert!(from_message("Usually used in the set phrase /ar ball/", None).is_empty());
+        assert!(from_message("Unknown word", Some("xyz")).is_empty());
+        assert!(from_message("Flagged because /bord/ is masculine", None).is_empty());
+        assert!(from_message("Possible cause /séimhiú/", None).is_empty());
+        assert!(from_message("Glossed as demean /ísligh/", None).is_empty());
+    }
+
+    #[test]
+    fn applies_named_mutations_to_last_flagged_word() {
+        assert_eq!(
+            from_message("Eclipsis missing", Some("ar an bord")),
+            vec!["ar an mbord"]
+        );
+        assert_eq!(
+            from_message("Séimhiú ar iarraidh", Some("mo bean")),
+            vec!["mo bhean"]
+        );
+        assert_eq!(
+            from_message("Unnecessary lenition", Some("an bhord")),
+            vec!["an bord"]
+        );
+        assert_eq!(
+            from_message("Urú gan ghá", Some("sa gcarr")),
+            vec!["sa carr"]
+        );
+        assert_eq!(
+            from_message("Prefix /h/ missing", Some("a athair")),
+            vec!["a hathair"]
+        );
+        assert_eq!(
+            from_message("Réamhlitir /t/ ar iarraidh", Some("an uisce")),
+            vec!["an t-uisce"]
+        );
+        assert_eq!(
+            from_message("Urú ar iarraidh", Some("i Éirinn")),
+            vec!["i nÉirinn"]
+        );
+    }
+
+    #[test]
+    fn skips_mutations_that_do_not_apply() {
+        assert!(from_message("Lenition missing", Some("an stad")).is_empty());
+        assert!(from_message("Lenition missing", Some("an bhord")).is_empty());
+        assert!(from_message("Eclipsis missing", Some("ar an lá")).is_empty());
+        assert!(from_message("Prefix /h/ missing", Some("a bord")).is_empty());
+    }
+}
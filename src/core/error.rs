@@ -1,11 +1,124 @@
 use thiserror::Error;
 
 /// Gateway-wide error model for uniform HTTP/JSON mapping.
-#[allow(dead_code)]
+///
+/// | variant               | JSON-RPC | HTTP |
+/// |-----------------------|----------|------|
+/// | `InvalidParams`       | -32602   | 400  |
+/// | `NotFound`            | -32002   | 404  |
+/// | `Unauthorized`        | -32006   | 401  |
+/// | `Forbidden`           | -32007   | 403  |
+/// | `RateLimited`         | -32005   | 429  |
+/// | `UpstreamUnavailable` | -32001   | 503  |
+/// | `UpstreamBadResponse` | -32003   | 502  |
+/// | `Timeout`             | -32004   | 504  |
+/// | `Message` (internal)  | -32603   | 500  |
 #[derive(Debug, Error)]
 pub enum GatewayError {
     #[error("{0}")]
     Message(String),
+    /// The caller's arguments are missing or malformed.
+    #[error("{0}")]
+    InvalidParams(String),
+    /// The named tool (or other resource) does not exist.
+    #[error("{0}")]
+    NotFound(String),
+    /// No valid credentials were presented.
+    #[error("{0}")]
+    Unauthorized(String),
+    /// The credentials are valid but do not cover the requested tool.
+    #[error("{0}")]
+    Forbidden(String),
+    /// The caller exceeded its rate limit or quota.
+    #[error("rate limited (retry in {retry_after_secs}s)")]
+    RateLimited { retry_after_secs: u64 },
+    /// The upstream could not be reached, kept failing, or its circuit breaker is open.
+    #[error("upstream unavailable: {upstream}: {reason}")]
+    UpstreamUnavailable {
+        upstream: String,
+        reason: String,
+        retry_after_secs: Option<u64>,
+    },
+    /// The upstream answered with an error status or an unusable body.
+    #[error("{reason}")]
+    UpstreamBadResponse { upstream: String, reason: String },
+    /// The upstream did not answer in time.
+    #[error("upstream timed out: {upstream}: {reason}")]
+    Timeout { upstream: String, reason: String },
+}
+
+impl GatewayError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            GatewayError::Message(_) => "internal",
+            GatewayError::InvalidParams(_) => "invalid_params",
+            GatewayError::NotFound(_) => "not_found",
+            GatewayError::Unauthorized(_) => "unauthorized",
+            GatewayError::Forbidden(_) => "forbidden",
+            GatewayError::RateLimited { .. } => "rate_limited",
+            GatewayError::UpstreamUnavailable { .. } => "upstream_unavailable",
+            GatewayError::UpstreamBadResponse { .. } => "upstream_bad_response",
+            GatewayError::Timeout { .. } => "timeout",
+        }
+    }
+
+    pub fn rpc_code(&self) -> i32 {
+        match self {
+            GatewayError::Message(_) => -32603,
+            GatewayError::InvalidParams(_) => -32602,
+            GatewayError::NotFound(_) => -32002,
+            GatewayError::Unauthorized(_) => -32006,
+            GatewayError::Forbidden(_) => -32007,
+            GatewayError::RateLimited { .. } => -32005,
+            GatewayError::UpstreamUnavailable { .. } => -32001,
+            GatewayError::UpstreamBadResponse { .. } => -32003,
+            GatewayError::Timeout { .. } => -32004,
+        }
+    }
+
+    pub fn http_status(&self) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
+        match self {
+            GatewayError::Message(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            GatewayError::InvalidParams(_) => StatusCode::BAD_REQUEST,
+            GatewayError::NotFound(_) => StatusCode::NOT_FOUND,
+            GatewayError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            GatewayError::Forbidden(_) => StatusCode::FORBIDDEN,
+            GatewayError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            GatewayError::UpstreamUnavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            GatewayError::UpstreamBadResponse { .. } => StatusCode::BAD_GATEWAY,
+            GatewayError::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+        }
+    }
+
+    /// Seconds a client should wait before retrying, when known.
+    pub fn retry_after_secs(&self) -> Option<u64> {
+        match self {
+            GatewayError::RateLimited { retry_after_secs } => Some(*retry_after_secs),
+            GatewayError::UpstreamUnavailable {
+                retry_after_secs, ..
+            } => *retry_after_secs,
+            _ => None,
+        }
+    }
+
+    /// Structured JSON-RPC `error.data`: the error `kind` plus any upstream and
+    /// retry hints.
+    pub fn rpc_data(&self) -> Option<serde_json::Value> {
+        let mut data = serde_json::json!({ "kind": self.kind() });
+        match self {
+            GatewayError::UpstreamUnavailable { upstream, .. }
+            | GatewayError::UpstreamBadResponse { upstream, .. }
+            | GatewayError::Timeout { upstream, .. } => {
+                data["upstream"] = upstream.clone().into();
+            }
+            _ => {}
+        }
+        if let Some(secs) = self.retry_after_secs() {
+            data["retry_after_secs"] = secs.into();
+        }
+        Some(data)
+    }
 }
 
 impl From<anyhow::Error> for GatewayError {
@@ -14,9 +127,32 @@ impl From<anyhow::Error> for GatewayError {
     }
 }
 
+impl From<String> for GatewayError {
+    fn from(e: String) -> Self {
+        GatewayError::Message(e)
+    }
+}
+
+impl From<&str> for GatewayError {
+    fn from(e: &str) -> Self {
+        GatewayError::Message(e.to_string())
+    }
+}
+
+impl From<GatewayError> for rmcp::ErrorData {
+    fn from(e: GatewayError) -> Self {
+        rmcp::ErrorData::new(
+            rmcp::model::ErrorCode(e.rpc_code()),
+            e.to_string(),
+            e.rpc_data(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::http::StatusCode;
 
     #[test]
     fn it_displays_message() {
@@ -30,4 +166,60 @@ mod tests {
         let gw: GatewayError = any.into();
         assert_eq!(gw.to_string(), "nope");
     }
+
+    #[test]
+    fn upstream_unavailable_maps_to_rmcp_error() {
+        let e = GatewayError::UpstreamUnavailable {
+            upstream: "http://gramadoir".into(),
+            reason: "circuit open".into(),
+            retry_after_secs: Some(12),
+        };
+        assert!(e.to_string().contains("upstream unavailable"));
+        assert_eq!(e.http_status(), StatusCode::SERVICE_UNAVAILABLE);
+        let data: rmcp::ErrorData = e.into();
+        assert_eq!(data.code.0, -32001);
+        let extra = data.data.unwrap();
+        assert_eq!(extra["kind"], "upstream_unavailable");
+        assert_eq!(extra["upstream"], "http://gramadoir");
+        assert_eq!(extra["retry_after_secs"], 12);
+    }
+
+    #[test]
+    fn every_variant_has_distinct_codes() {
+        let all = [
+            GatewayError::Message("x".into()),
+            GatewayError::InvalidParams("x".into()),
+            GatewayError::NotFound("x".into()),
+            GatewayError::Unauthorized("x".into()),
+            GatewayError::Forbidden("x".into()),
+            GatewayError::RateLimited {
+                retry_after_secs: 1,
+            },
+            GatewayError::UpstreamUnavailable {
+                upstream: "u".into(),
+                reason: "x".into(),
+                retry_after_secs: None,
+            },
+            GatewayError::UpstreamBadResponse {
+                upstream: "u".into(),
+                reason: "x".into(),
+            },
+            GatewayError::Timeout {
+                upstream: "u".into(),
+                reason: "x".into(),
+            },
+        ];
+        let mut codes: Vec<i32> = all.iter().map(|e| e.rpc_code()).collect();
+        let mut statuses: Vec<u16> = all.iter().map(|e| e.http_status().as_u16()).collect();
+        codes.sort();
+        codes.dedup();
+        statuses.sort();
+        statuses.dedup();
+        assert_eq!(codes.len(), all.len());
+        assert_eq!(statuses.len(), all.len());
+        assert_eq!(all[1].rpc_code(), -32602);
+        assert_eq!(all[4].http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(all[5].http_status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(all[5].rpc_data().unwrap()["retry_after_secs"], 1);
+    }
 }
@@ -1,24 +1,33 @@
 use async_trait::async_trait;
 
+use crate::core::error::GatewayError;
+
 /// Minimal metadata every tool must expose.
 pub trait ToolSpec {
     fn name(&self) -> &'static str;
     fn description(&self) -> &'static str;
     fn input_schema(&self) -> serde_json::Value;
+
+    /// JSON schema of the structured result returned by `call`, published as
+    /// `outputSchema`. Tools without a stable result shape may leave this unset.
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        None
+    }
 }
 
 /// Backend abstraction so a tool can be local or remote.
 #[allow(dead_code)]
 #[async_trait]
 pub trait ToolBackend: Send + Sync {
-    async fn call(&self, arguments: &serde_json::Value) -> Result<serde_json::Value, String>;
+    async fn call(&self, arguments: &serde_json::Value) -> Result<serde_json::Value, GatewayError>;
 }
 
 /// Tool = Spec + Backend implementation
 #[async_trait]
 pub trait Tool: ToolSpec + Send + Sync {
-    /// Execute the tool with the given arguments, returning a JSON value or a string error.
-    async fn call(&self, arguments: &serde_json::Value) -> Result<serde_json::Value, String>;
+    /// Execute the tool with the given arguments. Failures are `GatewayError`s, so
+    /// transports can map them to HTTP status and JSON-RPC error codes.
+    async fn call(&self, arguments: &serde_json::Value) -> Result<serde_json::Value, GatewayError>;
 
     /// Optional liveness/health probe for the tool.
     /// Defaults to healthy. Remote implementations should override.
@@ -48,7 +57,7 @@ mod tests {
 
     #[async_trait]
     impl Tool for Echo {
-        async fn call(&self, args: &serde_json::Value) -> Result<serde_json::Value, String> {
+        async fn call(&self, args: &serde_json::Value) -> Result<serde_json::Value, GatewayError> {
             Ok(args.clone())
         }
     }
@@ -76,7 +85,7 @@ mod tests {
 
     #[async_trait]
     impl Tool for Failing {
-        async fn call(&self, _args: &serde_json::Value) -> Result<serde_json::Value, String> {
+        async fn call(&self, _args: &serde_json::Value) -> Result<serde_json::Value, GatewayError> {
             Err("boom".into())
         }
     }
@@ -85,7 +94,7 @@ mod tests {
     async fn failing_propagates_error() {
         let t = Failing;
         let err = t.call(&serde_json::json!({})).await.unwrap_err();
-        assert!(err.contains("boom"));
+        assert!(err.to_string().contains("boom"));
     }
 
     #[test]
@@ -101,7 +110,10 @@ mod tests {
 
     #[async_trait]
     impl ToolBackend for BackendEcho {
-        async fn call(&self, arguments: &serde_json::Value) -> Result<serde_json::Value, String> {
+        async fn call(
+            &self,
+            arguments: &serde_json::Value,
+        ) -> Result<serde_json::Value, GatewayError> {
             Ok(arguments.clone())
         }
     }
@@ -117,7 +129,10 @@ mod tests {
 
     #[async_trait]
     impl ToolBackend for BackendFail {
-        async fn call(&self, _arguments: &serde_json::Value) -> Result<serde_json::Value, String> {
+        async fn call(
+            &self,
+            _arguments: &serde_json::Value,
+        ) -> Result<serde_json::Value, GatewayError> {
             Err("backend fail".into())
         }
     }
@@ -126,7 +141,7 @@ mod tests {
     async fn tool_backend_error_path() {
         let b = BackendFail;
         let err = b.call(&serde_json::json!({})).await.unwrap_err();
-        assert!(err.contains("fail"));
+        assert!(err.to_string().contains("fail"));
     }
 
     struct SpecAndBackend;
@@ -145,7 +160,10 @@ mod tests {
 
     #[async_trait]
     impl Tool for SpecAndBackend {
-        async fn call(&self, arguments: &serde_json::Value) -> Result<serde_json::Value, String> {
+        async fn call(
+            &self,
+            arguments: &serde_json::Value,
+        ) -> Result<serde_json::Value, GatewayError> {
             Ok(arguments.clone())
         }
     }
@@ -168,4 +186,9 @@ mod tests {
         assert_eq!(s["type"], "object");
         // Properties may vary per tool; requiring object type suffices here
     }
+
+    #[test]
+    fn output_schema_defaults_to_none() {
+        assert!(Echo.output_schema().is_none());
+    }
 }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+/// A grammar finding. `start`/`end` are char (Unicode scalar) offsets into the
+/// checked text; `utf16_start`/`utf16_end` are the same span in UTF-16 code units.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GrammarIssue {
     pub code: String,
@@ -7,15 +9,162 @@ pub struct GrammarIssue {
     pub start: usize,
     pub end: usize,
     #[serde(default)]
+    pub utf16_start: usize,
+    #[serde(default)]
+    pub utf16_end: usize,
+    #[serde(default)]
     pub suggestions: Vec<String>,
 }
 
+impl GrammarIssue {
+    /// JSON schema for `{"issues": [GrammarIssue]}` grammar results.
+    pub fn result_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "issues": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "code": { "type": "string" },
+                            "message": { "type": "string" },
+                            "start": { "type": "integer", "minimum": 0 },
+                            "end": { "type": "integer", "minimum": 0 },
+                            "utf16_start": { "type": "integer", "minimum": 0 },
+                            "utf16_end": { "type": "integer", "minimum": 0 },
+                            "suggestions": { "type": "array", "items": { "type": "string" } }
+                        },
+                        "required": ["code", "message", "start", "end", "utf16_start", "utf16_end", "suggestions"]
+                    }
+                }
+            },
+            "required": ["issues"]
+        })
+    }
+}
+
+/// A single misspelled token. `start`/`end` are char offsets, `byte_start`/`byte_end`
+/// are UTF-8 byte offsets into the checked text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpellCorrection {
+    pub token: String,
+    pub start: usize,
+    pub end: usize,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub reason: SpellReason,
+    /// Ranked best first.
+    #[serde(default)]
+    pub suggestions: Vec<SpellSuggestion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpellSuggestion {
+    pub word: String,
+    /// Confidence in `0.0..=1.0`; `None` when the backend does not score suggestions.
+    #[serde(default)]
+    pub score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpellReason {
+    /// The token is not in the dictionary.
+    UnknownWord,
+    /// The token is a known word once one or more fadas (acute accents) are added.
+    MissingFada,
+}
+
+impl SpellReason {
+    /// Classify a misspelling from its best suggestion.
+    pub fn classify(token: &str, best: Option<&str>) -> Self {
+        match best {
+            Some(best) if is_missing_fada(token, best) => SpellReason::MissingFada,
+            _ => SpellReason::UnknownWord,
+        }
+    }
+
+    /// The serialised name, e.g. `missing-fada`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SpellReason::UnknownWord => "unknown-word",
+            SpellReason::MissingFada => "missing-fada",
+        }
+    }
+}
+
+fn strip_fada(c: char) -> char {
+    match c {
+        'á' => 'a',
+        'é' => 'e',
+        'í' => 'i',
+        'ó' => 'o',
+        'ú' => 'u',
+        'Á' => 'A',
+        'É' => 'E',
+        'Í' => 'I',
+        'Ó' => 'O',
+        'Ú' => 'U',
+        other => other,
+    }
+}
+
+/// True when `candidate` equals `token` with only fadas added.
+fn is_missing_fada(token: &str, candidate: &str) -> bool {
+    let t: Vec<char> = token.chars().collect();
+    let c: Vec<char> = candidate.chars().collect();
+    t.len() == c.len()
+        && t != c
+        && t.iter()
+            .zip(&c)
+            .all(|(a, b)| a == b || *a == strip_fada(*b))
+}
+
+impl SpellCorrection {
+    /// JSON schema for `{"corrections": [SpellCorrection]}` spellcheck results.
+    pub fn result_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "corrections": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "token": { "type": "string" },
+                            "start": { "type": "integer", "minimum": 0 },
+                            "end": { "type": "integer", "minimum": 0 },
+                            "byte_start": { "type": "integer", "minimum": 0 },
+                            "byte_end": { "type": "integer", "minimum": 0 },
+                            "reason": { "type": "string", "enum": ["unknown-word", "missing-fada"] },
+                            "suggestions": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "word": { "type": "string" },
+                                        "score": { "type": ["number", "null"] }
+                                    },
+                                    "required": ["word"]
+                                }
+                            }
+                        },
+                        "required": ["token", "start", "end", "byte_start", "byte_end", "reason", "suggestions"]
+                    }
+                }
+            },
+            "required": ["corrections"]
+        })
+    }
+}
+
 // Legacy Tool trait removed - using core::tool::Tool instead
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde_json::{from_value, to_value};
+    use serde_json::{from_value, json, to_value};
 
     // ToolError test removed - using core::error::GatewayError instead
 
@@ -26,6 +175,8 @@ mod tests {
             message: "Agreement issue".into(),
             start: 1,
             end: 3,
+            utf16_start: 1,
+            utf16_end: 3,
             suggestions: vec!["X".into()],
         };
         let v = to_value(&gi).unwrap();
@@ -33,4 +184,63 @@ mod tests {
         assert_eq!(back.code, "AGR");
         assert_eq!(back.suggestions, vec!["X"]);
     }
+
+    #[test]
+    fn spell_reason_names_match_serde() {
+        for reason in [SpellReason::UnknownWord, SpellReason::MissingFada] {
+            assert_eq!(to_value(reason).unwrap(), reason.as_str());
+        }
+    }
+
+    #[test]
+    fn it_serializes_spell_correction_with_kebab_reason() {
+        let c = SpellCorrection {
+            token: "cota".into(),
+            start: 0,
+            end: 4,
+            byte_start: 0,
+            byte_end: 4,
+            reason: SpellReason::MissingFada,
+            suggestions: vec![SpellSuggestion {
+                word: "cóta".into(),
+                score: Some(0.95),
+            }],
+        };
+        let v = to_value(&c).unwrap();
+        assert_eq!(v["reason"], "missing-fada");
+        assert_eq!(v["suggestions"][0], json!({"word":"cóta","score":0.95}));
+        let back: SpellCorrection = from_value(v).unwrap();
+        assert_eq!(back, c);
+    }
+
+    #[test]
+    fn it_classifies_missing_fada() {
+        assert_eq!(
+            SpellReason::classify("cota", Some("cóta")),
+            SpellReason::MissingFada
+        );
+        assert_eq!(
+            SpellReason::classify("Eire", Some("Éire")),
+            SpellReason::MissingFada
+        );
+        assert_eq!(
+            SpellReason::classify("cóta", Some("cota")),
+            SpellReason::UnknownWord
+        );
+        assert_eq!(
+            SpellReason::classify("brod", Some("bord")),
+            SpellReason::UnknownWord
+        );
+        assert_eq!(SpellReason::classify("xyz", None), SpellReason::UnknownWord);
+    }
+
+    #[test]
+    fn result_schema_lists_required_fields() {
+        let s = SpellCorrection::result_schema();
+        let item = &s["properties"]["corrections"]["items"];
+        assert!(item["required"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("reason")));
+    }
 }
@@ -1,8 +1,10 @@
 use crate::infra::config::Config;
+use crate::tools::registry::live::LiveRegistry;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 
-pub async fn run_server() -> anyhow::Result<()> {
-    let cfg = Config::from_env();
+/// Serve until shutdown, reloading `config_path` on change or SIGHUP.
+pub async fn run_server(cfg: Config, config_path: Option<PathBuf>) -> anyhow::Result<()> {
     tracing::info!(
         mode = %cfg.mode,
         port = cfg.port,
@@ -10,30 +12,36 @@ pub async fn run_server() -> anyhow::Result<()> {
         "BOOT irish-mcp-gateway"
     );
 
+    let live = LiveRegistry::new(cfg.clone());
+    crate::infra::runtime::reload::spawn(config_path, live.clone());
+
     if cfg.mode == "stdio" {
-        let factory = || {
-            let base = std::env::var("GRAMADOIR_BASE_URL").unwrap_or_default();
-            let handler = crate::tools::grammar::tool_router::GrammarSvc {
-                checker: crate::clients::gramadoir::GramadoirRemote::new(base),
-            };
-            let tools = crate::tools::grammar::tool_router::GrammarSvc::router();
-            (handler, tools)
-        };
-        crate::infra::runtime::mcp_transport::serve_stdio(factory)
+        let handler = crate::tools::registry::tool_router::RegistrySvc::new(live);
+        crate::infra::runtime::mcp_transport::serve_stdio_handler(handler)
             .await
             .map_err(|e| anyhow::anyhow!(e))?;
         return Ok(());
     }
 
     let app = if cfg.deprecate_rest {
-        crate::infra::http_app::build_app_default()
+        crate::infra::http_app::build_app_default(&live)
     } else {
-        let registry = crate::tools::registry::build_registry();
-        crate::infra::http_app::build_app_with_deprecated_api(registry)
+        crate::infra::http_app::build_app_with_deprecated_api(&live)
     };
 
     let addr: SocketAddr = ([0, 0, 0, 0], cfg.port).into();
-    axum::serve(tokio::net::TcpListener::bind(addr).await?, app).await?;
+    // Peer addresses key rate limits for unauthenticated clients
+    axum::serve(
+        tokio::net::TcpListener::bind(addr).await?,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+    .await?;
+    if let Some(cache) = live.cache() {
+        tokio::task::spawn_blocking(move || cache.flush()).await?;
+    }
     Ok(())
 }
 
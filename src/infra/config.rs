@@ -1,31 +1,865 @@
+//! Gateway configuration.
+//!
+//! Settings are layered: built-in defaults, then an optional TOML file
+//! (`--config` / `CONFIG_PATH`), then environment variables. The resulting
+//! `Config` is built once at startup and passed to everything that needs it.
+//!
+//! ```toml
+//! [server]
+//! mode = "server"
+//! port = 8080
+//! deprecate_rest = false
+//!
+//! [upstreams.gramadoir]
+//! base_url = "http://grammar-service:8080"
+//! timeout_ms = 6000
+//!
+//! [upstreams.spellcheck]
+//! base_url = "http://spellcheck:8080"
+//!
+//! [tools.grammar]
+//! disabled_rules = ["IASACHT"]
+//! batch_concurrency = 8
+//! batch_max_texts = 100
+//!
+//! [tools.spellcheck]
+//! dict_path = "/usr/share/hunspell/ga_IE"
+//!
+//! [cache]
+//! capacity = 1024
+//! ttl_secs = 3600
+//!
+//! [limits]
+//! rate_per_sec = 5.0
+//! burst = 20
+//! daily_quota = 10000
+//!
+//! [limits.costs."gael.grammar_check"]
+//! base = 1
+//! per_1k_chars = 1
+//!
+//! [[auth.keys]]
+//! name = "ci"
+//! sha256 = "<hex SHA-256 of the key>"
+//! scopes = ["gael.grammar_check"]
+//!
+//! [auth.oauth]
+//! resource = "https://mcp.example.ie"
+//! issuer = "https://auth.example.ie"
+//! jwks_path = "/etc/irish-mcp/jwks.json"
+//!
+//! [auth.oauth.scopes]
+//! "mcp:grammar" = ["gael.grammar_check", "gael.apply_fixes"]
+//!
+//! [tracing]
+//! exporter = "otlp"
+//! otlp_endpoint = "http://otel-collector:4318"
+//!
+//! [logging]
+//! format = "json"
+//! level = "info"
+//! user_text = "hash"
+//!
+//! [logging.modules]
+//! "irish_mcp_gateway::clients" = "debug"
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+mod validate;
+
+pub use validate::ConfigProblem;
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Config {
     pub mode: String, // "server" or "stdio"
     pub port: u16,
     pub deprecate_rest: bool,
+    /// Gramadóir rule ids suppressed by default (`GRAMMAR_DISABLED_RULES`, comma separated).
+    pub grammar_disabled_rules: Vec<String>,
+    /// Upstream requests in flight per `texts` batch (`GRAMMAR_BATCH_CONCURRENCY`).
+    pub grammar_batch_concurrency: usize,
+    /// Most texts accepted in one `texts` batch (`GRAMMAR_BATCH_MAX_TEXTS`).
+    pub grammar_batch_max_texts: usize,
+    /// Only these tools are exposed when set (`TOOLS_ALLOW`, comma separated).
+    pub tools_allow: Option<Vec<String>>,
+    /// Tools never exposed, even if allowed (`TOOLS_DENY`, comma separated).
+    pub tools_deny: Vec<String>,
+    /// Hunspell dictionary base path for the local spellcheck (`SPELLCHECK_DICT_PATH`).
+    pub spellcheck_dict_path: Option<String>,
+    /// Result cache entries (`CACHE_CAPACITY`, 0 disables caching).
+    pub cache_capacity: usize,
+    /// Result cache entry lifetime (`CACHE_TTL_SECS`).
+    pub cache_ttl: Duration,
+    /// Optional file the result cache is persisted to (`CACHE_PATH`). Keys are
+    /// hashed, but cached results quote words from the checked text.
+    pub cache_path: Option<String>,
+    /// Gramadóir endpoint and HTTP settings (`GRAMADOIR_*`).
+    pub gramadoir: UpstreamConfig,
+    /// Remote spellcheck endpoint and HTTP settings (`SPELLCHECK_*`).
+    pub spellcheck: UpstreamConfig,
+    /// Keys accepted on `/mcp` and the REST route; authentication is off when empty.
+    pub api_keys: Vec<ApiKeyConfig>,
+    /// Inbound per-client rate limits and quotas (`[limits]`).
+    pub limits: RateLimitConfig,
+    /// OAuth 2.1 resource-server settings (`[auth.oauth]`); bearer JWTs are
+    /// only accepted when set.
+    pub oauth: Option<OAuthConfig>,
+    /// OpenTelemetry span export (`[tracing]`).
+    pub tracing: TracingConfig,
+    /// Log format, levels and how user text is logged (`[logging]`).
+    pub logging: LogConfig,
+    /// Environment overrides that were ignored because their values are
+    /// invalid; `validate` reports them.
+    pub env_problems: Vec<ConfigProblem>,
+}
+
+/// Where spans go. Spans and trace context propagation are always on; this
+/// only decides whether they leave the process.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TracingConfig {
+    /// `none`, `stdout` or `otlp` (`OTEL_TRACES_EXPORTER`).
+    pub exporter: String,
+    /// OTLP/HTTP collector base URL (`OTEL_EXPORTER_OTLP_ENDPOINT`); the
+    /// exporter's default, `http://localhost:4318`, when unset.
+    pub otlp_endpoint: Option<String>,
+    /// `service.name` resource attribute (`OTEL_SERVICE_NAME`).
+    pub service_name: String,
+    /// Share of new traces sampled, 0.0 to 1.0 (`OTEL_TRACES_SAMPLER_ARG`).
+    /// Inbound `traceparent` sampling decisions are always honoured.
+    pub sample_ratio: f64,
+}
+
+impl Default for TracingConfig {
+    fn default() -> Self {
+        Self {
+            exporter: "none".into(),
+            otlp_endpoint: None,
+            service_name: env!("CARGO_PKG_NAME").into(),
+            sample_ratio: 1.0,
+        }
+    }
+}
+
+impl TracingConfig {
+    fn apply_file(&mut self, file: &TracingFile) {
+        set(&mut self.exporter, file.exporter.clone());
+        if let Some(url) = &file.otlp_endpoint {
+            self.otlp_endpoint = non_empty(url.clone());
+        }
+        set(&mut self.service_name, file.service_name.clone());
+        set(&mut self.sample_ratio, file.sample_ratio);
+    }
+
+    fn apply_env(&mut self, env: &mut Env) {
+        if let Ok(url) = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            self.otlp_endpoint = non_empty(url);
+        }
+        if let Ok(exporter) = std::env::var("OTEL_TRACES_EXPORTER") {
+            self.exporter = exporter;
+        }
+        if let Ok(name) = std::env::var("OTEL_SERVICE_NAME") {
+            if let Some(name) = non_empty(name) {
+                self.service_name = name;
+            }
+        }
+        set(&mut self.sample_ratio, env.parse("OTEL_TRACES_SAMPLER_ARG"));
+    }
+}
+
+/// Log output. `RUST_LOG`, when set, replaces `level` and `modules`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogConfig {
+    /// `text` or `json`, one object per line (`LOG_FORMAT`).
+    pub format: String,
+    /// Level for everything not listed in `modules` (`LOG_LEVEL`).
+    pub level: String,
+    /// Levels by target, e.g. `"irish_mcp_gateway::clients" = "debug"`.
+    pub modules: BTreeMap<String, String>,
+    /// How users' text and tool output appear in logs: `redact`, `hash` or
+    /// `full` (`LOG_USER_TEXT`).
+    pub user_text: String,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            format: "text".into(),
+            level: "info".into(),
+            modules: BTreeMap::new(),
+            user_text: "redact".into(),
+        }
+    }
+}
+
+impl LogConfig {
+    /// `EnvFilter` directives for `level` and `modules`.
+    pub fn directives(&self) -> String {
+        std::iter::once(self.level.clone())
+            .chain(self.modules.iter().map(|(m, l)| format!("{m}={l}")))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn apply_file(&mut self, file: &LoggingFile) {
+        set(&mut self.format, file.format.clone());
+        set(&mut self.level, file.level.clone());
+        set(&mut self.modules, file.modules.clone());
+        set(&mut self.user_text, file.user_text.clone());
+    }
+
+    fn apply_env(&mut self) {
+        set(
+            &mut self.format,
+            std::env::var("LOG_FORMAT").ok().and_then(non_empty),
+        );
+        set(
+            &mut self.level,
+            std::env::var("LOG_LEVEL").ok().and_then(non_empty),
+        );
+        set(
+            &mut self.user_text,
+            std::env::var("LOG_USER_TEXT").ok().and_then(non_empty),
+        );
+    }
+}
+
+/// Token-bucket rate limits and daily quotas, applied per client (API key,
+/// MCP session or IP) to `tools/call` requests. Costs are in abstract units.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitConfig {
+    /// Units refilled per second; 0 disables rate limiting (`RATE_LIMIT_PER_SEC`).
+    pub rate_per_sec: f64,
+    /// Bucket size, i.e. the most a client can spend in one burst (`RATE_LIMIT_BURST`).
+    pub burst: f64,
+    /// Units per client per UTC day; 0 means unlimited (`DAILY_QUOTA`).
+    pub daily_quota: u64,
+    /// Per-tool costs; tools not listed cost 1 per call.
+    pub costs: BTreeMap<String, ToolCost>,
+}
+
+/// What one call to a tool costs: `base` plus `per_1k_chars` for every started
+/// thousand characters of `text`/`texts` input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolCost {
+    #[serde(default = "ToolCost::default_base")]
+    pub base: u64,
+    #[serde(default)]
+    pub per_1k_chars: u64,
+}
+
+impl ToolCost {
+    fn default_base() -> u64 {
+        1
+    }
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        // Gramadóir work grows with the text, so weight its tools by length
+        let by_length = ToolCost {
+            base: 1,
+            per_1k_chars: 1,
+        };
+        Self {
+            rate_per_sec: 0.0,
+            burst: 20.0,
+            daily_quota: 0,
+            costs: BTreeMap::from([
+                ("gael.grammar_check".to_string(), by_length),
+                ("gael.apply_fixes".to_string(), by_length),
+            ]),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    fn apply_file(&mut self, file: &LimitsFile) {
+        set(&mut self.rate_per_sec, file.rate_per_sec);
+        set(&mut self.burst, file.burst);
+        set(&mut self.daily_quota, file.daily_quota);
+        if let Some(costs) = &file.costs {
+            self.costs.extend(costs.clone());
+        }
+    }
+
+    fn apply_env(&mut self, env: &mut Env) {
+        set(&mut self.rate_per_sec, env.parse("RATE_LIMIT_PER_SEC"));
+        set(&mut self.burst, env.parse("RATE_LIMIT_BURST"));
+        set(&mut self.daily_quota, env.parse("DAILY_QUOTA"));
+    }
+
+    /// Whether any limit is in force.
+    pub fn enabled(&self) -> bool {
+        self.rate_per_sec > 0.0 || self.daily_quota > 0
+    }
+
+    /// Cost of calling `tool` with `args`.
+    pub fn cost(&self, tool: &str, args: &serde_json::Value) -> u64 {
+        let cost = self.costs.get(tool).copied().unwrap_or(ToolCost {
+            base: 1,
+            per_1k_chars: 0,
+        });
+        if cost.per_1k_chars == 0 {
+            return cost.base;
+        }
+        let texts = args.get("texts").and_then(|t| t.as_array());
+        let chars: usize = args
+            .get("text")
+            .into_iter()
+            .chain(texts.into_iter().flatten())
+            .filter_map(|t| t.as_str())
+            .map(|t| t.chars().count())
+            .sum();
+        cost.base + cost.per_1k_chars * chars.div_ceil(1000) as u64
+    }
+}
+
+/// Validation settings for OAuth access tokens issued to this server.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OAuthConfig {
+    /// This server's canonical URL; tokens must name it as their audience.
+    pub resource: String,
+    /// Authorization server whose tokens are accepted (the `iss` claim).
+    pub issuer: String,
+    /// Local JWKS file holding the issuer's signing keys.
+    pub jwks_path: String,
+    /// Clock skew allowed when checking `exp` and `nbf`.
+    #[serde(default = "OAuthConfig::default_leeway_secs")]
+    pub leeway_secs: u64,
+    /// OAuth scope → tools it grants (`"*"` for every tool). Any valid token
+    /// may use every tool when empty.
+    #[serde(default)]
+    pub scopes: BTreeMap<String, Vec<String>>,
+}
+
+impl OAuthConfig {
+    fn default_leeway_secs() -> u64 {
+        60
+    }
+}
+
+/// An accepted API key (`[[auth.keys]]`). Only the key's hash is stored.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// Label used in logs in place of the key.
+    pub name: String,
+    /// Hex-encoded SHA-256 of the key.
+    pub sha256: String,
+    /// Tools the key may list and call; every tool when omitted.
+    #[serde(default)]
+    pub scopes: Option<Vec<String>>,
+}
+
+/// Per-upstream settings, read from `<PREFIX>_BASE_URL`, `_CONNECT_TIMEOUT_MS`,
+/// `_TIMEOUT_MS`, `_RETRIES`, `_BACKOFF_CAP_MS`, `_RETRY_JITTER`,
+/// `_POOL_MAX_IDLE`, `_HTTP2`, `_MAX_CONCURRENT`, `_MAX_QUEUE`,
+/// `_QUEUE_TIMEOUT_MS` and `_CHUNK_CHARS`. Breaker settings come from
+/// `BREAKER_FAILURE_THRESHOLD` and `BREAKER_OPEN_SECS`, which apply to every
+/// upstream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpstreamConfig {
+    /// The upstream is only used when this is set.
+    pub base_url: Option<String>,
+    pub connect_timeout: Duration,
+    pub request_timeout: Duration,
+    /// Retries after the first attempt; only connect errors, timeouts and 5xx are retried.
+    pub retries: u32,
+    pub backoff_cap: Duration,
+    pub jitter: bool,
+    /// Idle keep-alive connections kept per host.
+    pub pool_max_idle: usize,
+    /// Speak HTTP/2 without negotiation (h2c prior knowledge).
+    pub http2: bool,
+    /// Consecutive outages that open the upstream's circuit breaker.
+    pub breaker_failure_threshold: u32,
+    /// How long an open breaker rejects calls before probing.
+    pub breaker_open_for: Duration,
+    /// Requests in flight to the upstream at once.
+    pub max_concurrent: usize,
+    /// Requests allowed to wait for a slot; any more are rejected as overloaded.
+    pub max_queue: usize,
+    /// How long a queued request waits for a slot before giving up.
+    pub queue_timeout: Duration,
+    /// Longest document, in chars, sent in one request (Gramadóir only);
+    /// longer ones are split on sentence boundaries.
+    pub chunk_chars: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("cannot read config file {path}: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("invalid config file {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+}
+
+impl Default for UpstreamConfig {
+    fn default() -> Self {
+        Self {
+            base_url: None,
+            connect_timeout: Duration::from_secs(2),
+            request_timeout: Duration::from_secs(6),
+            retries: 2,
+            backoff_cap: Duration::from_secs(1),
+            jitter: true,
+            pool_max_idle: 32,
+            http2: false,
+            breaker_failure_threshold: 5,
+            breaker_open_for: Duration::from_secs(30),
+            max_concurrent: 8,
+            max_queue: 64,
+            queue_timeout: Duration::from_secs(10),
+            chunk_chars: crate::clients::gramadoir::DEFAULT_CHUNK_CHARS,
+        }
+    }
+}
+
+impl UpstreamConfig {
+    fn apply_file(&mut self, file: &UpstreamFile) {
+        if let Some(url) = &file.base_url {
+            self.base_url = non_empty(url.clone());
+        }
+        set(
+            &mut self.connect_timeout,
+            file.connect_timeout_ms.map(Duration::from_millis),
+        );
+        set(
+            &mut self.request_timeout,
+            file.timeout_ms.map(Duration::from_millis),
+        );
+        set(&mut self.retries, file.retries);
+        set(
+            &mut self.backoff_cap,
+            file.backoff_cap_ms.map(Duration::from_millis),
+        );
+        set(&mut self.jitter, file.retry_jitter);
+        set(&mut self.pool_max_idle, file.pool_max_idle);
+        set(&mut self.http2, file.http2);
+        set(
+            &mut self.breaker_failure_threshold,
+            file.breaker_failure_threshold.filter(|n| *n > 0),
+        );
+        set(
+            &mut self.breaker_open_for,
+            file.breaker_open_secs.map(Duration::from_secs),
+        );
+        set(
+            &mut self.max_concurrent,
+            file.max_concurrent.filter(|n| *n > 0),
+        );
+        set(&mut self.max_queue, file.max_queue);
+        set(
+            &mut self.queue_timeout,
+            file.queue_timeout_ms.map(Duration::from_millis),
+        );
+        set(&mut self.chunk_chars, file.chunk_chars.filter(|n| *n > 0));
+    }
+
+    fn apply_env(&mut self, prefix: &str, env: &mut Env) {
+        let name = |suffix: &str| format!("{prefix}_{suffix}");
+        if let Ok(url) = std::env::var(name("BASE_URL")) {
+            self.base_url = non_empty(url);
+        }
+        set(
+            &mut self.connect_timeout,
+            env.millis(&name("CONNECT_TIMEOUT_MS")),
+        );
+        set(&mut self.request_timeout, env.millis(&name("TIMEOUT_MS")));
+        set(&mut self.retries, env.parse(&name("RETRIES")));
+        set(&mut self.backoff_cap, env.millis(&name("BACKOFF_CAP_MS")));
+        set(&mut self.jitter, env.flag(&name("RETRY_JITTER")));
+        set(&mut self.pool_max_idle, env.parse(&name("POOL_MAX_IDLE")));
+        set(&mut self.http2, env.flag(&name("HTTP2")));
+        set(
+            &mut self.breaker_failure_threshold,
+            env.positive("BREAKER_FAILURE_THRESHOLD"),
+        );
+        set(&mut self.breaker_open_for, env.secs("BREAKER_OPEN_SECS"));
+        set(
+            &mut self.max_concurrent,
+            env.positive(&name("MAX_CONCURRENT")),
+        );
+        set(&mut self.max_queue, env.parse(&name("MAX_QUEUE")));
+        set(
+            &mut self.queue_timeout,
+            env.millis(&name("QUEUE_TIMEOUT_MS")),
+        );
+        set(&mut self.chunk_chars, env.positive(&name("CHUNK_CHARS")));
+    }
+
+    pub fn retry_policy(&self) -> crate::infra::runtime::limits::RetryPolicy {
+        crate::infra::runtime::limits::RetryPolicy {
+            retries: self.retries,
+            backoff_cap: self.backoff_cap,
+            jitter: self.jitter,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            mode: "server".into(),
+            port: 8080,
+            deprecate_rest: false,
+            grammar_disabled_rules: Vec::new(),
+            grammar_batch_concurrency: crate::tools::grammar::batch::DEFAULT_CONCURRENCY,
+            grammar_batch_max_texts: crate::tools::grammar::batch::DEFAULT_MAX_TEXTS,
+            tools_allow: None,
+            tools_deny: Vec::new(),
+            spellcheck_dict_path: None,
+            cache_capacity: 1024,
+            cache_ttl: Duration::from_secs(3600),
+            cache_path: None,
+            // Gramadóir is single-threaded, so send it one request at a time
+            gramadoir: UpstreamConfig {
+                max_concurrent: 1,
+                ..UpstreamConfig::default()
+            },
+            spellcheck: UpstreamConfig::default(),
+            api_keys: Vec::new(),
+            limits: RateLimitConfig::default(),
+            oauth: None,
+            tracing: TracingConfig::default(),
+            logging: LogConfig::default(),
+            env_problems: Vec::new(),
+        }
+    }
 }
 
 impl Config {
+    /// Defaults, then the TOML file at `path` (if any), then environment overrides.
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let mut cfg = Self::default();
+        if let Some(path) = path {
+            cfg.apply_file(&FileConfig::read(path)?);
+        }
+        cfg.apply_env();
+        Ok(cfg)
+    }
+
+    /// Whether the allow/deny lists let `tool` be exposed.
+    pub fn tool_enabled(&self, tool: &str) -> bool {
+        let allowed = self
+            .tools_allow
+            .as_ref()
+            .is_none_or(|allow| allow.iter().any(|t| t == tool));
+        allowed && !self.tools_deny.iter().any(|t| t == tool)
+    }
+
+    /// Defaults overridden by environment variables, without a config file.
+    #[allow(dead_code)]
     pub fn from_env() -> Self {
-        let mode = std::env::var("MODE").unwrap_or_else(|_| "server".into());
-        let port = std::env::var("PORT")
-            .ok()
-            .and_then(|s| s.parse::<u16>().ok())
-            .unwrap_or(8080);
-        let deprecate_rest = std::env::var("DEPRECATE_REST")
-            .map(|v| !v.is_empty())
-            .unwrap_or(false);
+        let mut cfg = Self::default();
+        cfg.apply_env();
+        cfg
+    }
 
-        Self {
-            mode,
-            port,
-            deprecate_rest,
+    fn apply_file(&mut self, file: &FileConfig) {
+        set(&mut self.mode, file.server.mode.clone());
+        set(&mut self.port, file.server.port);
+        set(&mut self.deprecate_rest, file.server.deprecate_rest);
+        if let Some(allow) = &file.tools.allow {
+            self.tools_allow = Some(allow.clone());
+        }
+        set(&mut self.tools_deny, file.tools.deny.clone());
+        set(
+            &mut self.grammar_disabled_rules,
+            file.tools.grammar.disabled_rules.clone(),
+        );
+        set(
+            &mut self.grammar_batch_concurrency,
+            file.tools.grammar.batch_concurrency.filter(|n| *n > 0),
+        );
+        set(
+            &mut self.grammar_batch_max_texts,
+            file.tools.grammar.batch_max_texts.filter(|n| *n > 0),
+        );
+        if let Some(path) = &file.tools.spellcheck.dict_path {
+            self.spellcheck_dict_path = non_empty(path.clone());
+        }
+        set(&mut self.cache_capacity, file.cache.capacity);
+        set(
+            &mut self.cache_ttl,
+            file.cache.ttl_secs.map(Duration::from_secs),
+        );
+        if let Some(path) = &file.cache.path {
+            self.cache_path = non_empty(path.clone());
+        }
+        self.gramadoir.apply_file(&file.upstreams.gramadoir);
+        self.spellcheck.apply_file(&file.upstreams.spellcheck);
+        self.limits.apply_file(&file.limits);
+        set(&mut self.api_keys, file.auth.keys.clone());
+        if let Some(oauth) = &file.auth.oauth {
+            self.oauth = Some(oauth.clone());
+        }
+        self.tracing.apply_file(&file.tracing);
+        self.logging.apply_file(&file.logging);
+    }
+
+    fn apply_env(&mut self) {
+        let mut env = Env::default();
+        if let Ok(mode) = std::env::var("MODE") {
+            self.mode = mode;
+        }
+        set(&mut self.port, env.parse("PORT"));
+        if let Ok(v) = std::env::var("DEPRECATE_REST") {
+            self.deprecate_rest = !v.is_empty();
+        }
+        set(
+            &mut self.grammar_disabled_rules,
+            env_list("GRAMMAR_DISABLED_RULES"),
+        );
+        if let Some(allow) = env_list("TOOLS_ALLOW") {
+            self.tools_allow = Some(allow);
+        }
+        set(&mut self.tools_deny, env_list("TOOLS_DENY"));
+        set(
+            &mut self.grammar_batch_concurrency,
+            env.positive("GRAMMAR_BATCH_CONCURRENCY"),
+        );
+        set(
+            &mut self.grammar_batch_max_texts,
+            env.positive("GRAMMAR_BATCH_MAX_TEXTS"),
+        );
+        if let Ok(path) = std::env::var("SPELLCHECK_DICT_PATH") {
+            self.spellcheck_dict_path = non_empty(path);
+        }
+        set(&mut self.cache_capacity, env.parse("CACHE_CAPACITY"));
+        set(&mut self.cache_ttl, env.secs("CACHE_TTL_SECS"));
+        if let Ok(path) = std::env::var("CACHE_PATH") {
+            self.cache_path = non_empty(path);
+        }
+        self.gramadoir.apply_env("GRAMADOIR", &mut env);
+        self.spellcheck.apply_env("SPELLCHECK", &mut env);
+        self.limits.apply_env(&mut env);
+        self.tracing.apply_env(&mut env);
+        self.logging.apply_env();
+        self.env_problems = env.problems;
+    }
+}
+
+/// On-disk shape of the config file. Every key is optional; unknown keys are
+/// rejected so typos don't go unnoticed.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    server: ServerFile,
+    upstreams: UpstreamsFile,
+    tools: ToolsFile,
+    cache: CacheFile,
+    limits: LimitsFile,
+    auth: AuthFile,
+    tracing: TracingFile,
+    logging: LoggingFile,
+}
+
+impl FileConfig {
+    fn read(path: &Path) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        toml::from_str(&text).map_err(|source| ConfigError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ServerFile {
+    mode: Option<String>,
+    port: Option<u16>,
+    deprecate_rest: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct UpstreamsFile {
+    gramadoir: UpstreamFile,
+    spellcheck: UpstreamFile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct UpstreamFile {
+    base_url: Option<String>,
+    connect_timeout_ms: Option<u64>,
+    timeout_ms: Option<u64>,
+    retries: Option<u32>,
+    backoff_cap_ms: Option<u64>,
+    retry_jitter: Option<bool>,
+    pool_max_idle: Option<usize>,
+    http2: Option<bool>,
+    breaker_failure_threshold: Option<u32>,
+    breaker_open_secs: Option<u64>,
+    max_concurrent: Option<usize>,
+    max_queue: Option<usize>,
+    queue_timeout_ms: Option<u64>,
+    chunk_chars: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ToolsFile {
+    allow: Option<Vec<String>>,
+    deny: Option<Vec<String>>,
+    grammar: GrammarFile,
+    spellcheck: SpellcheckFile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct GrammarFile {
+    disabled_rules: Option<Vec<String>>,
+    batch_concurrency: Option<usize>,
+    batch_max_texts: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SpellcheckFile {
+    dict_path: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CacheFile {
+    capacity: Option<usize>,
+    ttl_secs: Option<u64>,
+    path: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LimitsFile {
+    rate_per_sec: Option<f64>,
+    burst: Option<f64>,
+    daily_quota: Option<u64>,
+    costs: Option<BTreeMap<String, ToolCost>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TracingFile {
+    exporter: Option<String>,
+    otlp_endpoint: Option<String>,
+    service_name: Option<String>,
+    sample_ratio: Option<f64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LoggingFile {
+    format: Option<String>,
+    level: Option<String>,
+    modules: Option<BTreeMap<String, String>>,
+    user_text: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AuthFile {
+    keys: Option<Vec<ApiKeyConfig>>,
+    oauth: Option<OAuthConfig>,
+}
+
+fn set<T>(slot: &mut T, value: Option<T>) {
+    if let Some(value) = value {
+        *slot = value;
+    }
+}
+
+fn non_empty(s: String) -> Option<String> {
+    Some(s).filter(|s| !s.trim().is_empty())
+}
+
+fn env_list(name: &str) -> Option<Vec<String>> {
+    std::env::var(name).ok().map(|v| {
+        v.split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(String::from)
+            .collect()
+    })
+}
+
+/// Reads environment overrides, noting any that cannot be used so
+/// `Config::validate` can report them instead of silently keeping the default.
+#[derive(Default)]
+struct Env {
+    problems: Vec<ConfigProblem>,
+}
+
+impl Env {
+    fn parse<T: std::str::FromStr>(&mut self, name: &str) -> Option<T> {
+        let value = std::env::var(name).ok()?;
+        let parsed = value.trim().parse().ok();
+        if parsed.is_none() {
+            self.invalid(name, &value, "is not a valid value");
+        }
+        parsed
+    }
+
+    /// Like `parse`, for settings that must be greater than zero.
+    fn positive<T: std::str::FromStr + Default + PartialEq>(&mut self, name: &str) -> Option<T> {
+        let n = self.parse(name)?;
+        if n == T::default() {
+            self.invalid(name, "0", "must be greater than 0");
+            return None;
+        }
+        Some(n)
+    }
+
+    fn millis(&mut self, name: &str) -> Option<Duration> {
+        self.parse(name).map(Duration::from_millis)
+    }
+
+    fn secs(&mut self, name: &str) -> Option<Duration> {
+        self.parse(name).map(Duration::from_secs)
+    }
+
+    fn flag(&mut self, name: &str) -> Option<bool> {
+        let value = std::env::var(name).ok()?;
+        match value.trim() {
+            "1" | "true" | "yes" => Some(true),
+            "0" | "false" | "no" | "" => Some(false),
+            _ => {
+                self.invalid(name, &value, "must be true or false");
+                None
+            }
+        }
+    }
+
+    fn invalid(&mut self, name: &str, value: &str, why: &str) {
+        // Shared variables such as `BREAKER_OPEN_SECS` are read once per upstream
+        if self.problems.iter().all(|p| p.field != name) {
+            self.problems
+                .push(ConfigProblem::new(name, format!("'{value}' {why}")));
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Config;
+    use super::{Config, ConfigError, TracingConfig, UpstreamConfig};
     use serial_test::serial;
 
     #[test]
@@ -39,12 +873,38 @@ mod tests {
         assert_eq!(cfg.mode, "server");
         assert_eq!(cfg.port, 8080);
         assert!(!cfg.deprecate_rest);
+        assert!(cfg.grammar_disabled_rules.is_empty());
+        assert_eq!(cfg.grammar_batch_concurrency, 8);
+        assert_eq!(cfg.grammar_batch_max_texts, 100);
+        assert_eq!(cfg.gramadoir.max_concurrent, 1);
+        assert_eq!(cfg.spellcheck.max_concurrent, 8);
+        assert_eq!(cfg.cache_capacity, 1024);
+        assert_eq!(cfg.cache_ttl.as_secs(), 3600);
+        assert!(cfg.cache_path.is_none());
+        assert_eq!(cfg.gramadoir.breaker_failure_threshold, 5);
+        assert_eq!(cfg.gramadoir.breaker_open_for.as_secs(), 30);
 
         // Overrides when provided
         std::env::set_var("MODE", "stdio");
         std::env::set_var("PORT", "9090");
         std::env::set_var("DEPRECATE_REST", "1");
+        std::env::set_var("GRAMMAR_DISABLED_RULES", "IASACHT, ,BACHOIR");
+        std::env::set_var("GRAMMAR_BATCH_CONCURRENCY", "32");
+        std::env::set_var("GRAMMAR_BATCH_MAX_TEXTS", "10");
+        std::env::set_var("CACHE_CAPACITY", "16");
+        std::env::set_var("CACHE_TTL_SECS", "5");
+        std::env::set_var("CACHE_PATH", "/tmp/cache.json");
+        std::env::set_var("BREAKER_FAILURE_THRESHOLD", "3");
+        std::env::set_var("BREAKER_OPEN_SECS", "10");
         let cfg2 = Config::from_env();
+        assert_eq!(cfg2.gramadoir.breaker_failure_threshold, 3);
+        assert_eq!(cfg2.spellcheck.breaker_open_for.as_secs(), 10);
+        assert_eq!(cfg2.cache_capacity, 16);
+        assert_eq!(cfg2.cache_ttl.as_secs(), 5);
+        assert_eq!(cfg2.cache_path.as_deref(), Some("/tmp/cache.json"));
+        assert_eq!(cfg2.grammar_batch_concurrency, 32);
+        assert_eq!(cfg2.grammar_batch_max_texts, 10);
+        assert_eq!(cfg2.grammar_disabled_rules, vec!["IASACHT", "BACHOIR"]);
         assert_eq!(cfg2.mode, "stdio");
         assert_eq!(cfg2.port, 9090);
         assert!(cfg2.deprecate_rest);
@@ -53,5 +913,312 @@ mod tests {
         std::env::remove_var("MODE");
         std::env::remove_var("PORT");
         std::env::remove_var("DEPRECATE_REST");
+        std::env::remove_var("GRAMMAR_DISABLED_RULES");
+        std::env::remove_var("GRAMMAR_BATCH_CONCURRENCY");
+        std::env::remove_var("GRAMMAR_BATCH_MAX_TEXTS");
+        std::env::remove_var("CACHE_CAPACITY");
+        std::env::remove_var("CACHE_TTL_SECS");
+        std::env::remove_var("CACHE_PATH");
+        std::env::remove_var("BREAKER_FAILURE_THRESHOLD");
+        std::env::remove_var("BREAKER_OPEN_SECS");
+    }
+
+    #[test]
+    #[serial]
+    fn it_reports_invalid_env_overrides() {
+        let vars = [
+            ("GRAMADOIR_TIMEOUT_MS", "5s"),
+            ("BREAKER_OPEN_SECS", "x"),
+            ("SPELLCHECK_HTTP2", "maybe"),
+            ("GRAMADOIR_MAX_CONCURRENT", "0"),
+            ("RATE_LIMIT_PER_SEC", "fast"),
+        ];
+        for (name, value) in vars {
+            std::env::set_var(name, value);
+        }
+        let cfg = Config::from_env();
+        let defaults = Config::default();
+        assert_eq!(
+            cfg.gramadoir.request_timeout,
+            defaults.gramadoir.request_timeout
+        );
+        assert_eq!(
+            cfg.gramadoir.breaker_open_for,
+            defaults.gramadoir.breaker_open_for
+        );
+        assert_eq!(cfg.gramadoir.max_concurrent, 1);
+        assert!(!cfg.spellcheck.http2);
+
+        let mut fields: Vec<_> = cfg.env_problems.iter().map(|p| p.field.as_str()).collect();
+        fields.sort_unstable();
+        let mut expected: Vec<_> = vars.iter().map(|(name, _)| *name).collect();
+        expected.sort_unstable();
+        // `BREAKER_OPEN_SECS` applies to both upstreams but is reported once.
+        assert_eq!(fields, expected);
+        assert!(cfg.env_problems[0].message.contains("'5s'"));
+        assert_eq!(cfg.validate().len(), vars.len());
+        for (name, _) in vars {
+            std::env::remove_var(name);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn it_parses_per_upstream_http_settings() {
+        std::env::set_var("GRAMADOIR_BASE_URL", "http://gramadoir");
+        std::env::set_var("GRAMADOIR_TIMEOUT_MS", "15000");
+        std::env::set_var("GRAMADOIR_RETRIES", "0");
+        std::env::set_var("GRAMADOIR_HTTP2", "true");
+        std::env::set_var("GRAMADOIR_RETRY_JITTER", "0");
+        std::env::set_var("SPELLCHECK_BASE_URL", " ");
+        std::env::set_var("SPELLCHECK_POOL_MAX_IDLE", "4");
+        std::env::set_var("GRAMADOIR_MAX_CONCURRENT", "2");
+        std::env::set_var("GRAMADOIR_MAX_QUEUE", "5");
+        std::env::set_var("GRAMADOIR_QUEUE_TIMEOUT_MS", "2500");
+        std::env::set_var("GRAMADOIR_CHUNK_CHARS", "0");
+        let cfg = Config::from_env();
+        assert_eq!(cfg.gramadoir.base_url.as_deref(), Some("http://gramadoir"));
+        assert_eq!(cfg.gramadoir.request_timeout.as_millis(), 15000);
+        assert_eq!(cfg.gramadoir.retries, 0);
+        assert!(cfg.gramadoir.http2);
+        assert!(!cfg.gramadoir.jitter);
+        assert_eq!(
+            cfg.gramadoir.connect_timeout,
+            UpstreamConfig::default().connect_timeout
+        );
+        assert!(cfg.spellcheck.base_url.is_none());
+        assert_eq!(cfg.spellcheck.pool_max_idle, 4);
+        assert_eq!(cfg.spellcheck.retries, 2);
+        assert_eq!(cfg.gramadoir.max_concurrent, 2);
+        assert_eq!(cfg.gramadoir.max_queue, 5);
+        assert_eq!(cfg.gramadoir.queue_timeout.as_millis(), 2500);
+        assert_eq!(cfg.spellcheck.max_concurrent, 8);
+        assert_eq!(
+            cfg.gramadoir.chunk_chars,
+            UpstreamConfig::default().chunk_chars
+        );
+        for var in [
+            "GRAMADOIR_BASE_URL",
+            "GRAMADOIR_TIMEOUT_MS",
+            "GRAMADOIR_RETRIES",
+            "GRAMADOIR_HTTP2",
+            "GRAMADOIR_RETRY_JITTER",
+            "SPELLCHECK_BASE_URL",
+            "SPELLCHECK_POOL_MAX_IDLE",
+            "GRAMADOIR_MAX_CONCURRENT",
+            "GRAMADOIR_MAX_QUEUE",
+            "GRAMADOIR_QUEUE_TIMEOUT_MS",
+            "GRAMADOIR_CHUNK_CHARS",
+        ] {
+            std::env::remove_var(var);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn it_reads_otel_settings_from_env() {
+        assert_eq!(Config::from_env().tracing, TracingConfig::default());
+        std::env::set_var("OTEL_TRACES_EXPORTER", "otlp");
+        std::env::set_var("OTEL_EXPORTER_OTLP_ENDPOINT", "http://collector:4318");
+        std::env::set_var("OTEL_SERVICE_NAME", "gateway-eu");
+        std::env::set_var("OTEL_TRACES_SAMPLER_ARG", "0.25");
+        let cfg = Config::from_env();
+        assert_eq!(cfg.tracing.exporter, "otlp");
+        assert_eq!(
+            cfg.tracing.otlp_endpoint.as_deref(),
+            Some("http://collector:4318")
+        );
+        assert_eq!(cfg.tracing.service_name, "gateway-eu");
+        assert_eq!(cfg.tracing.sample_ratio, 0.25);
+        for var in [
+            "OTEL_TRACES_EXPORTER",
+            "OTEL_EXPORTER_OTLP_ENDPOINT",
+            "OTEL_SERVICE_NAME",
+            "OTEL_TRACES_SAMPLER_ARG",
+        ] {
+            std::env::remove_var(var);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn it_reads_rate_limits_from_env() {
+        std::env::set_var("RATE_LIMIT_PER_SEC", "0.5");
+        std::env::set_var("RATE_LIMIT_BURST", "4");
+        std::env::set_var("DAILY_QUOTA", "100");
+        let cfg = Config::from_env();
+        assert!(cfg.limits.enabled());
+        assert_eq!(cfg.limits.rate_per_sec, 0.5);
+        assert_eq!(cfg.limits.burst, 4.0);
+        assert_eq!(cfg.limits.daily_quota, 100);
+        for var in ["RATE_LIMIT_PER_SEC", "RATE_LIMIT_BURST", "DAILY_QUOTA"] {
+            std::env::remove_var(var);
+        }
+        assert!(!Config::from_env().limits.enabled());
+    }
+
+    #[test]
+    fn it_weights_tool_costs_by_text_length() {
+        let limits = Config::default().limits;
+        let text = |n: usize| serde_json::json!({ "text": "a".repeat(n) });
+        assert_eq!(limits.cost("hello.echo", &text(5000)), 1);
+        assert_eq!(limits.cost("gael.grammar_check", &text(0)), 1);
+        assert_eq!(limits.cost("gael.grammar_check", &text(1000)), 2);
+        assert_eq!(limits.cost("gael.grammar_check", &text(1001)), 3);
+        let batch = serde_json::json!({ "texts": ["a".repeat(600), "b".repeat(600)] });
+        assert_eq!(limits.cost("gael.apply_fixes", &batch), 3);
+    }
+
+    fn write_config(name: &str, body: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "irish-mcp-config-{}-{name}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, body).unwrap();
+        path
+    }
+
+    #[test]
+    #[serial]
+    fn it_layers_file_under_env_overrides() {
+        let path = write_config(
+            "layers",
+            r#"
+                [server]
+                mode = "stdio"
+                port = 9000
+
+                [upstreams.gramadoir]
+                base_url = "http://from-file"
+                timeout_ms = 1500
+                breaker_failure_threshold = 2
+                max_concurrent = 1
+                max_queue = 0
+                chunk_chars = 500
+
+                [tools.grammar]
+                disabled_rules = ["IASACHT"]
+
+                [tools.spellcheck]
+                dict_path = "/dicts/ga_IE"
+
+                [cache]
+                capacity = 64
+
+                [limits]
+                rate_per_sec = 2.5
+                daily_quota = 500
+
+                [limits.costs."gael.spellcheck.v1"]
+                per_1k_chars = 2
+
+                [[auth.keys]]
+                name = "ci"
+                sha256 = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+                scopes = ["gael.grammar_check"]
+
+                [auth.oauth]
+                resource = "https://mcp.example.ie"
+                issuer = "https://auth.example.ie"
+                jwks_path = "/etc/irish-mcp/jwks.json"
+
+                [auth.oauth.scopes]
+                "mcp:grammar" = ["gael.grammar_check"]
+            "#,
+        );
+        std::env::remove_var("MODE");
+        std::env::set_var("PORT", "9100");
+        let cfg = Config::load(Some(&path)).unwrap();
+        assert_eq!(cfg.mode, "stdio");
+        assert_eq!(cfg.port, 9100);
+        assert_eq!(cfg.gramadoir.base_url.as_deref(), Some("http://from-file"));
+        assert_eq!(cfg.gramadoir.request_timeout.as_millis(), 1500);
+        assert_eq!(cfg.gramadoir.breaker_failure_threshold, 2);
+        assert_eq!(cfg.gramadoir.retries, 2);
+        assert_eq!(cfg.gramadoir.max_concurrent, 1);
+        assert_eq!(cfg.gramadoir.max_queue, 0);
+        assert_eq!(cfg.gramadoir.chunk_chars, 500);
+        assert!(cfg.spellcheck.base_url.is_none());
+        assert_eq!(cfg.grammar_disabled_rules, vec!["IASACHT"]);
+        assert_eq!(cfg.spellcheck_dict_path.as_deref(), Some("/dicts/ga_IE"));
+        assert_eq!(cfg.cache_capacity, 64);
+        assert_eq!(cfg.cache_ttl.as_secs(), 3600);
+        assert_eq!(cfg.limits.rate_per_sec, 2.5);
+        assert_eq!(cfg.limits.burst, 20.0);
+        assert_eq!(cfg.limits.daily_quota, 500);
+        assert_eq!(cfg.limits.costs["gael.spellcheck.v1"].base, 1);
+        assert_eq!(cfg.limits.costs["gael.spellcheck.v1"].per_1k_chars, 2);
+        assert!(cfg.limits.costs.contains_key("gael.grammar_check"));
+        assert_eq!(cfg.api_keys.len(), 1);
+        assert_eq!(cfg.api_keys[0].name, "ci");
+        assert_eq!(
+            cfg.api_keys[0].scopes.as_deref(),
+            Some(&["gael.grammar_check".to_string()][..])
+        );
+        let oauth = cfg.oauth.as_ref().unwrap();
+        assert_eq!(oauth.issuer, "https://auth.example.ie");
+        assert_eq!(oauth.leeway_secs, 60);
+        assert_eq!(oauth.scopes["mcp:grammar"], vec!["gael.grammar_check"]);
+
+        std::env::set_var("GRAMADOIR_BASE_URL", "http://from-env");
+        let cfg = Config::load(Some(&path)).unwrap();
+        assert_eq!(cfg.gramadoir.base_url.as_deref(), Some("http://from-env"));
+
+        std::env::remove_var("PORT");
+        std::env::remove_var("GRAMADOIR_BASE_URL");
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    #[serial]
+    fn it_builds_log_filters_from_file_and_env() {
+        let path = write_config(
+            "logging",
+            r#"
+                [logging]
+                format = "json"
+                level = "warn"
+                user_text = "hash"
+
+                [logging.modules]
+                "irish_mcp_gateway::clients" = "debug"
+                rmcp = "error"
+            "#,
+        );
+        let cfg = Config::load(Some(&path)).unwrap();
+        assert_eq!(cfg.logging.format, "json");
+        assert_eq!(cfg.logging.user_text, "hash");
+        assert_eq!(
+            cfg.logging.directives(),
+            "warn,irish_mcp_gateway::clients=debug,rmcp=error"
+        );
+
+        std::env::set_var("LOG_FORMAT", "text");
+        std::env::set_var("LOG_LEVEL", "debug");
+        std::env::set_var("LOG_USER_TEXT", "full");
+        let cfg = Config::load(Some(&path)).unwrap();
+        assert_eq!(cfg.logging.format, "text");
+        assert_eq!(cfg.logging.user_text, "full");
+        assert!(cfg.logging.directives().starts_with("debug,"));
+        for var in ["LOG_FORMAT", "LOG_LEVEL", "LOG_USER_TEXT"] {
+            std::env::remove_var(var);
+        }
+        assert_eq!(Config::from_env().logging.directives(), "info");
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn it_reports_unreadable_and_invalid_files() {
+        let missing = std::env::temp_dir().join("irish-mcp-config-does-not-exist.toml");
+        assert!(matches!(
+            Config::load(Some(&missing)),
+            Err(ConfigError::Read { .. })
+        ));
+
+        let path = write_config("typo", "[server]\nprot = 8080\n");
+        let err = Config::load(Some(&path)).unwrap_err();
+        assert!(matches!(err, ConfigError::Parse { .. }));
+        assert!(err.to_string().contains("prot"));
+        let _ = std::fs::remove_file(path);
     }
 }
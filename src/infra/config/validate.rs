@@ -0,0 +1,424 @@
+//! `config --validate` checks. Every problem is collected rather than stopping
+//! at the first, and each is keyed by its config file path.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::{Config, ConfigError, OAuthConfig, UpstreamConfig};
+use crate::tools::registry::TOOL_NAMES;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigProblem {
+    /// Config file key the problem belongs to, e.g. `upstreams.gramadoir.base_url`,
+    /// or the environment variable for an invalid override, e.g. `PORT`.
+    pub field: String,
+    pub message: String,
+}
+
+impl ConfigProblem {
+    pub(crate) fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl From<&ConfigError> for ConfigProblem {
+    fn from(err: &ConfigError) -> Self {
+        Self::new("config", err.to_string())
+    }
+}
+
+impl std::fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+impl Config {
+    /// `Config::load`, rejecting a config that fails `validate`. Used both at
+    /// startup and on reload, so neither serves a config the other would refuse.
+    pub fn load_valid(path: Option<&Path>) -> Result<Config, Vec<ConfigProblem>> {
+        let cfg = Config::load(path).map_err(|e| vec![ConfigProblem::from(&e)])?;
+        let problems = cfg.validate();
+        if !problems.is_empty() {
+            return Err(problems);
+        }
+        Ok(cfg)
+    }
+
+    /// Check everything that can be checked without starting the gateway.
+    pub fn validate(&self) -> Vec<ConfigProblem> {
+        let mut problems = self.env_problems.clone();
+
+        if !matches!(self.mode.as_str(), "server" | "stdio") {
+            problems.push(ConfigProblem::new(
+                "server.mode",
+                format!("Invalid MODE: {}. Must be 'server' or 'stdio'", self.mode),
+            ));
+        }
+        if self.mode == "server" && self.port == 0 {
+            problems.push(ConfigProblem::new("server.port", "PORT cannot be 0"));
+        }
+
+        check_upstream("gramadoir", &self.gramadoir, &mut problems);
+        check_upstream("spellcheck", &self.spellcheck, &mut problems);
+
+        let allow = self
+            .tools_allow
+            .iter()
+            .flatten()
+            .map(|n| ("tools.allow", n));
+        let deny = self.tools_deny.iter().map(|n| ("tools.deny", n));
+        for (field, name) in allow.chain(deny) {
+            if !TOOL_NAMES.contains(&name.as_str()) {
+                problems.push(ConfigProblem::new(
+                    field,
+                    format!("unknown tool '{name}' (known: {})", TOOL_NAMES.join(", ")),
+                ));
+            }
+        }
+
+        if let Some(base) = &self.spellcheck_dict_path {
+            for ext in ["aff", "dic"] {
+                let path = Path::new(base).with_extension(ext);
+                if let Err(e) = std::fs::File::open(&path) {
+                    problems.push(ConfigProblem::new(
+                        "tools.spellcheck.dict_path",
+                        format!("cannot read {}: {e}", path.display()),
+                    ));
+                }
+            }
+        }
+
+        if let Some(path) = &self.cache_path {
+            check_cache_path(Path::new(path), &mut problems);
+        }
+
+        for (i, key) in self.api_keys.iter().enumerate() {
+            let field = |name: &str| format!("auth.keys[{i}].{name}");
+            if key.name.trim().is_empty() {
+                problems.push(ConfigProblem::new(field("name"), "must not be empty"));
+            }
+            if key.sha256.len() != 64 || !key.sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+                problems.push(ConfigProblem::new(
+                    field("sha256"),
+                    "must be a hex-encoded SHA-256 (64 characters)",
+                ));
+            }
+            for scope in key.scopes.iter().flatten() {
+                if !TOOL_NAMES.contains(&scope.as_str()) {
+                    problems.push(ConfigProblem::new(
+                        field("scopes"),
+                        format!("unknown tool '{scope}'"),
+                    ));
+                }
+            }
+        }
+
+        let limits = &self.limits;
+        if !(limits.rate_per_sec >= 0.0 && limits.rate_per_sec.is_finite()) {
+            problems.push(ConfigProblem::new(
+                "limits.rate_per_sec",
+                "must be a finite number >= 0",
+            ));
+        }
+        if limits.rate_per_sec > 0.0 && (limits.burst.is_nan() || limits.burst < 1.0) {
+            problems.push(ConfigProblem::new("limits.burst", "must be at least 1"));
+        }
+        for tool in limits.costs.keys() {
+            if !TOOL_NAMES.contains(&tool.as_str()) {
+                problems.push(ConfigProblem::new(
+                    format!("limits.costs.{tool}"),
+                    format!("unknown tool '{tool}'"),
+                ));
+            }
+        }
+
+        if let Some(oauth) = &self.oauth {
+            check_oauth(oauth, &mut problems);
+        }
+
+        let tracing = &self.tracing;
+        if !matches!(tracing.exporter.as_str(), "none" | "stdout" | "otlp") {
+            problems.push(ConfigProblem::new(
+                "tracing.exporter",
+                format!(
+                    "unknown exporter '{}'; use 'none', 'stdout' or 'otlp'",
+                    tracing.exporter
+                ),
+            ));
+        }
+        if tracing.exporter == "stdout" && self.mode == "stdio" {
+            problems.push(ConfigProblem::new(
+                "tracing.exporter",
+                "'stdout' would corrupt the JSON-RPC stream in stdio mode; use 'otlp' or 'none'",
+            ));
+        }
+        if let Some(url) = &tracing.otlp_endpoint {
+            if reqwest::Url::parse(url).is_err() {
+                problems.push(ConfigProblem::new(
+                    "tracing.otlp_endpoint",
+                    format!("invalid URL {url}"),
+                ));
+            }
+        }
+        if !(0.0..=1.0).contains(&tracing.sample_ratio) {
+            problems.push(ConfigProblem::new(
+                "tracing.sample_ratio",
+                "must be between 0 and 1",
+            ));
+        }
+
+        let logging = &self.logging;
+        if !matches!(logging.format.as_str(), "text" | "json") {
+            problems.push(ConfigProblem::new(
+                "logging.format",
+                format!("unknown format '{}'; use 'text' or 'json'", logging.format),
+            ));
+        }
+        let levels = std::iter::once(("logging.level".to_string(), &logging.level)).chain(
+            logging
+                .modules
+                .iter()
+                .map(|(module, level)| (format!("logging.modules.{module}"), level)),
+        );
+        for (field, level) in levels {
+            if level
+                .parse::<tracing_subscriber::filter::LevelFilter>()
+                .is_err()
+            {
+                problems.push(ConfigProblem::new(
+                    field,
+                    format!("unknown level '{level}'; use trace, debug, info, warn, error or off"),
+                ));
+            }
+        }
+        if !matches!(logging.user_text.as_str(), "redact" | "hash" | "full") {
+            problems.push(ConfigProblem::new(
+                "logging.user_text",
+                format!(
+                    "unknown policy '{}'; use 'redact', 'hash' or 'full'",
+                    logging.user_text
+                ),
+            ));
+        }
+
+        problems
+    }
+}
+
+fn check_oauth(cfg: &OAuthConfig, problems: &mut Vec<ConfigProblem>) {
+    match reqwest::Url::parse(&cfg.resource) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.host_str().is_some() => {}
+        _ => problems.push(ConfigProblem::new(
+            "auth.oauth.resource",
+            format!("must be an absolute http(s) URL, got '{}'", cfg.resource),
+        )),
+    }
+    if cfg.issuer.trim().is_empty() {
+        problems.push(ConfigProblem::new("auth.oauth.issuer", "must not be empty"));
+    }
+    if let Err(e) = crate::infra::http::oauth::load_jwks(Path::new(&cfg.jwks_path)) {
+        problems.push(ConfigProblem::new("auth.oauth.jwks_path", e));
+    }
+    for (scope, tools) in &cfg.scopes {
+        for tool in tools {
+            if tool != "*" && !TOOL_NAMES.contains(&tool.as_str()) {
+                problems.push(ConfigProblem::new(
+                    format!("auth.oauth.scopes.{scope}"),
+                    format!("unknown tool '{tool}'"),
+                ));
+            }
+        }
+    }
+}
+
+fn check_upstream(name: &str, cfg: &UpstreamConfig, problems: &mut Vec<ConfigProblem>) {
+    let field = |key: &str| format!("upstreams.{name}.{key}");
+    if let Some(url) = &cfg.base_url {
+        match reqwest::Url::parse(url) {
+            Ok(parsed) if !matches!(parsed.scheme(), "http" | "https") => {
+                problems.push(ConfigProblem::new(
+                    field("base_url"),
+                    format!("unsupported scheme '{}' in {url}", parsed.scheme()),
+                ))
+            }
+            Ok(parsed) if parsed.host_str().is_none() => problems.push(ConfigProblem::new(
+                field("base_url"),
+                format!("missing host in {url}"),
+            )),
+            Ok(_) => {}
+            Err(e) => problems.push(ConfigProblem::new(
+                field("base_url"),
+                format!("invalid URL {url}: {e}"),
+            )),
+        }
+    }
+    if cfg.connect_timeout.is_zero() {
+        problems.push(ConfigProblem::new(
+            field("connect_timeout_ms"),
+            "must be greater than 0",
+        ));
+    }
+    if cfg.request_timeout.is_zero() {
+        problems.push(ConfigProblem::new(
+            field("timeout_ms"),
+            "must be greater than 0",
+        ));
+    }
+    if cfg.max_queue > 0 && cfg.queue_timeout.is_zero() {
+        problems.push(ConfigProblem::new(
+            field("queue_timeout_ms"),
+            "must be greater than 0 when max_queue is set",
+        ));
+    }
+}
+
+/// The cache file may not exist yet, but its directory must, and an existing
+/// file must be readable.
+fn check_cache_path(path: &Path, problems: &mut Vec<ConfigProblem>) {
+    if path.exists() {
+        if let Err(e) = std::fs::File::open(path) {
+            problems.push(ConfigProblem::new(
+                "cache.path",
+                format!("cannot read {}: {e}", path.display()),
+            ));
+        }
+        return;
+    }
+    let dir = path
+        .parent()
+        .filter(|d| !d.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    if !dir.is_dir() {
+        problems.push(ConfigProblem::new(
+            "cache.path",
+            format!("directory {} does not exist", dir.display()),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::config::ApiKeyConfig;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn default_config_is_valid() {
+        assert!(Config::default().validate().is_empty());
+    }
+
+    #[test]
+    fn it_reports_every_problem() {
+        let mut cfg = Config {
+            mode: "daemon".into(),
+            tools_allow: Some(vec!["gael.grammar_check".into(), "gael.nope".into()]),
+            tools_deny: vec!["hello.wave".into()],
+            spellcheck_dict_path: Some("/definitely/not/here/ga_IE".into()),
+            cache_path: Some("/definitely/not/here/cache.json".into()),
+            api_keys: vec![ApiKeyConfig {
+                name: "ci".into(),
+                sha256: "not-a-hash".into(),
+                scopes: Some(vec!["gael.nope".into()]),
+            }],
+            oauth: Some(OAuthConfig {
+                resource: "/mcp".into(),
+                issuer: "https://auth.example.ie".into(),
+                jwks_path: "/definitely/not/here/jwks.json".into(),
+                leeway_secs: 60,
+                scopes: BTreeMap::from([("mcp:all".into(), vec!["*".into(), "nope".into()])]),
+            }),
+            ..Config::default()
+        };
+        cfg.gramadoir.base_url = Some("not a url".into());
+        cfg.spellcheck.base_url = Some("ftp://spell".into());
+        cfg.spellcheck.request_timeout = std::time::Duration::ZERO;
+        cfg.limits.rate_per_sec = 1.0;
+        cfg.limits.burst = 0.5;
+        cfg.tracing.exporter = "jaeger".into();
+        cfg.tracing.sample_ratio = 1.5;
+        cfg.logging.format = "logfmt".into();
+        cfg.logging
+            .modules
+            .insert("irish_mcp_gateway::clients".into(), "loud".into());
+        cfg.logging.user_text = "mask".into();
+
+        let fields: Vec<String> = cfg.validate().into_iter().map(|p| p.field).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "server.mode",
+                "upstreams.gramadoir.base_url",
+                "upstreams.spellcheck.base_url",
+                "upstreams.spellcheck.timeout_ms",
+                "tools.allow",
+                "tools.deny",
+                "tools.spellcheck.dict_path",
+                "tools.spellcheck.dict_path",
+                "cache.path",
+                "auth.keys[0].sha256",
+                "auth.keys[0].scopes",
+                "limits.burst",
+                "auth.oauth.resource",
+                "auth.oauth.jwks_path",
+                "auth.oauth.scopes.mcp:all",
+                "tracing.exporter",
+                "tracing.sample_ratio",
+                "logging.format",
+                "logging.modules.irish_mcp_gateway::clients",
+                "logging.user_text",
+            ]
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn load_valid_refuses_configs_with_problems() {
+        assert!(Config::load_valid(None).is_ok());
+
+        std::env::set_var("PORT", "0");
+        let problems = Config::load_valid(None).unwrap_err();
+        std::env::remove_var("PORT");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].field, "server.port");
+
+        let missing = Path::new("/definitely/not/here/gateway.toml");
+        let problems = Config::load_valid(Some(missing)).unwrap_err();
+        assert_eq!(problems[0].field, "config");
+    }
+
+    #[test]
+    fn it_rejects_stdout_spans_in_stdio_mode() {
+        let mut cfg = Config {
+            mode: "stdio".into(),
+            ..Config::default()
+        };
+        cfg.tracing.exporter = "stdout".into();
+        let problems = cfg.validate();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].field, "tracing.exporter");
+
+        cfg.mode = "server".into();
+        assert!(cfg.validate().is_empty());
+    }
+
+    #[test]
+    fn it_accepts_readable_dictionary_and_cache_paths() {
+        let dir = std::env::temp_dir().join(format!("irish-mcp-validate-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ga_IE.aff"), "").unwrap();
+        std::fs::write(dir.join("ga_IE.dic"), "0\n").unwrap();
+
+        let cfg = Config {
+            spellcheck_dict_path: Some(dir.join("ga_IE").display().to_string()),
+            cache_path: Some(dir.join("cache.json").display().to_string()),
+            ..Config::default()
+        };
+        assert!(cfg.validate().is_empty());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,381 @@
+//! Authentication for `/mcp` and the REST route.
+//!
+//! API keys are presented as `Authorization: Bearer <key>` or `X-API-Key: <key>`
+//! and matched by SHA-256 against `[[auth.keys]]`. When `[auth.oauth]` is set,
+//! a bearer token that is not an API key is validated as an OAuth access token
+//! (see [`oauth`](super::oauth)). Either way the caller's scopes limit which
+//! tools it may call (rejected here with 403) and list (filtered by the
+//! handlers through the [`Principal`] request extension). With neither
+//! configured every request is let through.
+
+use axum::body::{to_bytes, Body};
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::core::error::GatewayError;
+use crate::infra::config::Config;
+use crate::infra::http::json as http_json;
+use crate::infra::http::oauth;
+use crate::tools::registry::live::LiveRegistry;
+
+/// Largest request body inspected for `tools/call` scope checks.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// The API key or token subject a request authenticated as.
+#[derive(Debug, Clone)]
+pub struct Principal {
+    pub name: String,
+    /// Tools this caller may use; every tool when `None`.
+    scopes: Option<Vec<String>>,
+}
+
+impl Principal {
+    pub fn new(name: impl Into<String>, scopes: Option<Vec<String>>) -> Self {
+        Self {
+            name: name.into(),
+            scopes,
+        }
+    }
+
+    pub fn allows(&self, tool: &str) -> bool {
+        self.scopes
+            .as_ref()
+            .is_none_or(|scopes| scopes.iter().any(|s| s == tool))
+    }
+}
+
+/// Whether a request may use `tool`; requests without a principal only get
+/// this far when authentication is off.
+pub fn permits(principal: Option<&Principal>, tool: &str) -> bool {
+    principal.is_none_or(|p| p.allows(tool))
+}
+
+/// Hex-encoded SHA-256 of `key`, as stored in `auth.keys[].sha256`.
+pub fn hash_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Middleware rejecting requests without valid credentials (401) or calling a
+/// tool outside the caller's scopes (403). Credentials are checked against the
+/// live config, so reloads take effect immediately.
+pub async fn require_auth(State(live): State<LiveRegistry>, req: Request, next: Next) -> Response {
+    let cfg = live.config();
+    if cfg.api_keys.is_empty() && cfg.oauth.is_none() {
+        return next.run(req).await;
+    }
+
+    let principal = match authenticate(&cfg, req.headers()) {
+        Ok(principal) => principal,
+        Err(err) => {
+            tracing::debug!(error = %err, "rejected unauthenticated request");
+            return reject(&cfg, Value::Null, err);
+        }
+    };
+
+    let (parts, body) = req.into_parts();
+    let bytes = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return reject(
+                &cfg,
+                Value::Null,
+                GatewayError::InvalidParams(format!("unreadable request body: {e}")),
+            )
+        }
+    };
+    if let Some((id, tool)) = forbidden_call(&bytes, &principal) {
+        tracing::warn!(caller = %principal.name, tool = %tool, "tool call outside caller's scopes");
+        return reject(
+            &cfg,
+            id,
+            GatewayError::Forbidden(format!("'{}' may not call {tool}", principal.name)),
+        );
+    }
+
+    let mut req = Request::from_parts(parts, Body::from(bytes));
+    req.extensions_mut().insert(principal);
+    next.run(req).await
+}
+
+/// Match the presented credential against the API keys, then (for bearer
+/// tokens) against the OAuth settings.
+fn authenticate(cfg: &Config, headers: &HeaderMap) -> Result<Principal, GatewayError> {
+    let (presented, bearer) = presented_key(headers)
+        .ok_or_else(|| GatewayError::Unauthorized("missing credentials".into()))?;
+    let hash = hash_key(presented);
+    if let Some(key) = cfg
+        .api_keys
+        .iter()
+        .find(|k| k.sha256.eq_ignore_ascii_case(&hash))
+    {
+        return Ok(Principal::new(key.name.clone(), key.scopes.clone()));
+    }
+    match &cfg.oauth {
+        Some(oauth) if bearer => oauth::verify(oauth, presented),
+        _ => Err(GatewayError::Unauthorized("invalid API key".into())),
+    }
+}
+
+/// The presented credential and whether it came as a bearer token.
+fn presented_key(headers: &HeaderMap) -> Option<(&str, bool)> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|v| (v, true));
+    let api_key = || {
+        let v = headers.get("x-api-key")?.to_str().ok()?;
+        Some((v, false))
+    };
+    bearer
+        .or_else(api_key)
+        .map(|(k, bearer)| (k.trim(), bearer))
+        .filter(|(k, _)| !k.is_empty())
+}
+
+/// The id and tool of the first `tools/call` in a (possibly batched) JSON-RPC
+/// body that `principal` may not call.
+fn forbidden_call(body: &[u8], principal: &Principal) -> Option<(Value, String)> {
+    let messages = match serde_json::from_slice(body).ok()? {
+        Value::Array(batch) => batch,
+        single => vec![single],
+    };
+    messages.into_iter().find_map(|msg| {
+        let method = msg.get("method")?.as_str()?;
+        if !matches!(method, "tools/call" | "tools.call") {
+            return None;
+        }
+        let tool = msg.pointer("/params/name")?.as_str()?;
+        (!principal.allows(tool))
+            .then(|| (msg.get("id").cloned().unwrap_or(Value::Null), tool.into()))
+    })
+}
+
+fn reject(cfg: &Config, id: Value, err: GatewayError) -> Response {
+    let challenge = challenge(cfg, &err);
+    let mut resp = http_json::error_response(id, err);
+    if let Some(value) = challenge.and_then(|c| HeaderValue::from_str(&c).ok()) {
+        resp.headers_mut().insert(header::WWW_AUTHENTICATE, value);
+    }
+    resp
+}
+
+/// `WWW-Authenticate` for 401/403 responses (RFC 6750), pointing OAuth
+/// clients at our protected-resource metadata (RFC 9728).
+fn challenge(cfg: &Config, err: &GatewayError) -> Option<String> {
+    let error = match err {
+        GatewayError::Unauthorized(msg) if msg.starts_with("invalid token") => {
+            Some("invalid_token")
+        }
+        GatewayError::Unauthorized(_) => None,
+        GatewayError::Forbidden(_) if cfg.oauth.is_some() => Some("insufficient_scope"),
+        _ => return None,
+    };
+    let mut params = Vec::new();
+    if let Some(oauth) = &cfg.oauth {
+        params.push(format!(
+            "resource_metadata=\"{}\"",
+            oauth::metadata_url(oauth)
+        ));
+    }
+    if let Some(error) = error {
+        params.push(format!("error=\"{error}\""));
+    }
+    if params.is_empty() {
+        Some("Bearer".into())
+    } else {
+        Some(format!("Bearer {}", params.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::config::ApiKeyConfig;
+    use crate::infra::http::oauth::tests::{claims, oauth_config, token};
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    fn app(keys: Vec<ApiKeyConfig>) -> Router {
+        app_with(Config {
+            api_keys: keys,
+            ..Config::default()
+        })
+    }
+
+    fn app_with(cfg: Config) -> Router {
+        let live = LiveRegistry::new(cfg);
+        Router::new()
+            .route(
+                "/rpc",
+                post(|req: Request| async move {
+                    let who = req.extensions().get::<Principal>().map(|p| p.name.clone());
+                    who.unwrap_or_else(|| "anonymous".into())
+                }),
+            )
+            .route_layer(axum::middleware::from_fn_with_state(live, require_auth))
+    }
+
+    fn key(name: &str, secret: &str, scopes: Option<&[&str]>) -> ApiKeyConfig {
+        ApiKeyConfig {
+            name: name.into(),
+            sha256: hash_key(secret),
+            scopes: scopes.map(|s| s.iter().map(|t| t.to_string()).collect()),
+        }
+    }
+
+    fn call(tool: &str) -> Body {
+        Body::from(format!(
+            r#"{{"jsonrpc":"2.0","id":5,"method":"tools/call","params":{{"name":"{tool}"}}}}"#
+        ))
+    }
+
+    async fn send(app: Router, auth: Option<(&str, &str)>, body: Body) -> (StatusCode, Value) {
+        let (status, _, json) = send_full(app, auth, body).await;
+        (status, json)
+    }
+
+    async fn send_full(
+        app: Router,
+        auth: Option<(&str, &str)>,
+        body: Body,
+    ) -> (StatusCode, Option<String>, Value) {
+        let mut req = Request::builder().method("POST").uri("/rpc");
+        if let Some((name, value)) = auth {
+            req = req.header(name, value);
+        }
+        let resp = app.oneshot(req.body(body).unwrap()).await.unwrap();
+        let status = resp.status();
+        let challenge = resp
+            .headers()
+            .get(header::WWW_AUTHENTICATE)
+            .map(|v| v.to_str().unwrap().to_string());
+        let bytes = to_bytes(resp.into_body(), 4096).await.unwrap();
+        let json = serde_json::from_slice(&bytes)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into()));
+        (status, challenge, json)
+    }
+
+    #[test]
+    fn hash_key_is_hex_sha256() {
+        assert_eq!(
+            hash_key("test"),
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        );
+    }
+
+    #[tokio::test]
+    async fn requests_pass_when_no_keys_are_configured() {
+        let (status, body) = send(app(vec![]), None, call("hello.echo")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "anonymous");
+    }
+
+    #[tokio::test]
+    async fn missing_or_unknown_keys_get_401() {
+        let keys = vec![key("ci", "s3cret", None)];
+        let (status, body) = send(app(keys.clone()), None, call("hello.echo")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["code"], -32006);
+
+        let (status, body) = send(
+            app(keys),
+            Some(("authorization", "Bearer wrong")),
+            call("hello.echo"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["data"]["kind"], "unauthorized");
+    }
+
+    #[tokio::test]
+    async fn bearer_and_header_keys_authenticate() {
+        let keys = vec![key("ci", "s3cret", None)];
+        let (status, body) = send(
+            app(keys.clone()),
+            Some(("authorization", "Bearer s3cret")),
+            call("hello.echo"),
+        )
+        .await;
+        assert_eq!((status, body), (StatusCode::OK, Value::from("ci")));
+
+        let (status, _) = send(app(keys), Some(("x-api-key", "s3cret")), call("x")).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn calls_outside_scopes_get_403() {
+        let keys = vec![key("grammar-only", "s3cret", Some(&["gael.grammar_check"]))];
+        let auth = Some(("x-api-key", "s3cret"));
+        let (status, _) = send(app(keys.clone()), auth, call("gael.grammar_check")).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = send(app(keys.clone()), auth, call("hello.echo")).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["id"], 5);
+        assert_eq!(body["error"]["code"], -32007);
+
+        // A forbidden call hidden in a batch is rejected too
+        let batch = r#"[{"jsonrpc":"2.0","id":1,"method":"tools/list"},
+            {"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"hello.echo"}}]"#;
+        let (status, body) = send(app(keys), auth, Body::from(batch)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["id"], 2);
+    }
+
+    #[tokio::test]
+    async fn oauth_tokens_authenticate_and_carry_scopes() {
+        let app = || {
+            app_with(Config {
+                oauth: Some(oauth_config("auth-layer")),
+                ..Config::default()
+            })
+        };
+        let bearer = format!("Bearer {}", token(claims("mcp:echo", 300)));
+        let auth = Some(("authorization", bearer.as_str()));
+
+        let (status, _, body) = send_full(app(), auth, call("hello.echo")).await;
+        assert_eq!((status, body), (StatusCode::OK, Value::from("aoife")));
+
+        let (status, challenge, body) = send_full(app(), auth, call("gael.grammar_check")).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["code"], -32007);
+        assert!(challenge.unwrap().contains("error=\"insufficient_scope\""));
+
+        let (status, challenge, _) = send_full(app(), None, call("hello.echo")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            challenge.unwrap(),
+            "Bearer resource_metadata=\"https://mcp.example.ie/.well-known/oauth-protected-resource\""
+        );
+
+        let expired = format!("Bearer {}", token(claims("mcp:echo", -120)));
+        let (status, challenge, body) = send_full(
+            app(),
+            Some(("authorization", expired.as_str())),
+            call("hello.echo"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["code"], -32006);
+        assert!(challenge.unwrap().contains("error=\"invalid_token\""));
+
+        // API keys never fall through to token validation
+        let (status, _, _) = send_full(
+            app(),
+            Some(("x-api-key", bearer.as_str())),
+            call("hello.echo"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -1,5 +1,7 @@
 use reqwest::RequestBuilder;
 
+use crate::infra::telemetry;
+
 /// Generate a simple request id suitable for logging/correlation.
 pub fn generate_request_id() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -9,13 +11,18 @@ pub fn generate_request_id() -> String {
     format!("gw-{}-{}", now.as_secs(), now.subsec_nanos())
 }
 
-/// Add standard headers to an outgoing request. Returns the updated builder and the request id used.
+/// Add standard headers to an outgoing request: the request id (by default the
+/// inbound request's, else a new one), `traceparent` for the current span and
+/// our user agent. Returns the updated builder and the request id used.
 pub fn add_standard_headers(
     builder: RequestBuilder,
     request_id: Option<String>,
 ) -> (RequestBuilder, String) {
-    let rid = request_id.unwrap_or_else(generate_request_id);
-    let b = builder.header("x-request-id", rid.as_str()).header(
+    let rid = request_id
+        .or_else(telemetry::current_request_id)
+        .unwrap_or_else(generate_request_id);
+    let b = telemetry::inject(builder);
+    let b = b.header("x-request-id", rid.as_str()).header(
         reqwest::header::USER_AGENT,
         format!("irish-mcp-gateway/{}", env!("CARGO_PKG_VERSION")),
     );
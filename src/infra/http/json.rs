@@ -24,9 +24,24 @@ pub fn parse_error(message: impl Into<String>) -> Json<RpcResp> {
     })
 }
 
-/// Map a GatewayError into a JSON-RPC error response (-32000 application error)
+/// Map a GatewayError into a JSON-RPC error response using its code and `data`
+/// (see `GatewayError::rpc_code`).
 pub fn from_gateway_error(id: serde_json::Value, err: GatewayError) -> Json<RpcResp> {
-    error(id, -32000, err.to_string())
+    Json(rpc_err(id, err.rpc_code(), err.to_string(), err.rpc_data()))
+}
+
+/// A JSON-RPC error response carrying the error's HTTP status, plus
+/// `Retry-After` when the error has a retry hint.
+pub fn error_response(id: serde_json::Value, err: GatewayError) -> axum::response::Response {
+    use axum::response::IntoResponse;
+    let status = err.http_status();
+    let retry_after = err.retry_after_secs();
+    let mut resp = (status, from_gateway_error(id, err)).into_response();
+    if let Some(secs) = retry_after {
+        resp.headers_mut()
+            .insert(axum::http::header::RETRY_AFTER, secs.into());
+    }
+    resp
 }
 
 #[cfg(test)]
@@ -53,6 +68,45 @@ mod tests {
         assert!(err.message.contains("method not found"));
     }
 
+    #[test]
+    fn maps_upstream_unavailable_with_data() {
+        let AxumJson(resp) = from_gateway_error(
+            json!(7),
+            GatewayError::UpstreamUnavailable {
+                upstream: "http://gramadoir".into(),
+                reason: "circuit open".into(),
+                retry_after_secs: Some(3),
+            },
+        );
+        let err = resp.error.unwrap();
+        assert_eq!(err.code, -32001);
+        let data = err.data.unwrap();
+        assert_eq!(data["upstream"], "http://gramadoir");
+        assert_eq!(data["retry_after_secs"], 3);
+
+        let AxumJson(resp) = from_gateway_error(json!(8), GatewayError::Message("boom".into()));
+        assert_eq!(resp.error.unwrap().code, -32603);
+    }
+
+    #[test]
+    fn error_response_sets_status_and_retry_after() {
+        let resp = error_response(
+            json!(1),
+            GatewayError::RateLimited {
+                retry_after_secs: 4,
+            },
+        );
+        assert_eq!(resp.status(), axum::http::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers()[axum::http::header::RETRY_AFTER], "4");
+
+        let resp = error_response(json!(2), GatewayError::NotFound("x".into()));
+        assert_eq!(resp.status(), axum::http::StatusCode::NOT_FOUND);
+        assert!(resp
+            .headers()
+            .get(axum::http::header::RETRY_AFTER)
+            .is_none());
+    }
+
     #[test]
     fn builds_parse_error_with_standard_code() {
         let AxumJson(resp) = parse_error("bad json");
@@ -0,0 +1,244 @@
+//! OAuth 2.1 resource-server support for the MCP HTTP transport.
+//!
+//! Access tokens are JWTs checked against a local JWKS file for signature,
+//! audience (our `resource`), issuer, expiry and not-before. Their scopes are
+//! mapped to tools through `[auth.oauth.scopes]`. Protected-resource metadata
+//! (RFC 9728) is served at [`METADATA_PATH`] so clients can find the
+//! authorization server.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, DecodingKey, Validation};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::core::error::GatewayError;
+use crate::infra::config::OAuthConfig;
+use crate::infra::http::auth::Principal;
+use crate::tools::registry::live::LiveRegistry;
+
+pub const METADATA_PATH: &str = "/.well-known/oauth-protected-resource";
+
+#[derive(Debug, Deserialize)]
+struct Claims {
+    sub: Option<String>,
+    client_id: Option<String>,
+    /// Space-separated scopes (RFC 9068).
+    scope: Option<String>,
+    /// Array form used by some authorization servers.
+    scp: Option<Vec<String>>,
+}
+
+/// Validate `token` and turn its scopes into the tools it may use.
+pub fn verify(cfg: &OAuthConfig, token: &str) -> Result<Principal, GatewayError> {
+    let invalid = |reason: String| GatewayError::Unauthorized(format!("invalid token: {reason}"));
+
+    let header = decode_header(token).map_err(|e| invalid(e.to_string()))?;
+    let jwks = load_jwks(Path::new(&cfg.jwks_path)).map_err(|e| {
+        tracing::error!(error = %e, "cannot load OAuth JWKS");
+        GatewayError::Message("token verification unavailable".into())
+    })?;
+    let jwk = match &header.kid {
+        Some(kid) => jwks.find(kid),
+        None if jwks.keys.len() == 1 => jwks.keys.first(),
+        None => None,
+    }
+    .ok_or_else(|| invalid("no matching signing key".into()))?;
+    let key = DecodingKey::from_jwk(jwk).map_err(|e| invalid(e.to_string()))?;
+
+    // The key family check in `decode` stops algorithm confusion (e.g. an
+    // HS256 token signed with an RSA public key).
+    let mut validation = Validation::new(header.alg);
+    validation.set_audience(&[&cfg.resource]);
+    validation.set_issuer(&[&cfg.issuer]);
+    validation.set_required_spec_claims(&["exp", "aud", "iss"]);
+    validation.validate_nbf = true;
+    validation.leeway = cfg.leeway_secs;
+    let claims = decode::<Claims>(token, &key, &validation)
+        .map_err(|e| match e.kind() {
+            ErrorKind::ExpiredSignature => invalid("token expired".into()),
+            ErrorKind::InvalidAudience => invalid("wrong audience".into()),
+            ErrorKind::InvalidIssuer => invalid("wrong issuer".into()),
+            _ => invalid(e.to_string()),
+        })?
+        .claims;
+
+    let granted: Vec<String> = match (claims.scope, claims.scp) {
+        (Some(scope), _) => scope.split_whitespace().map(String::from).collect(),
+        (None, Some(scp)) => scp,
+        (None, None) => Vec::new(),
+    };
+    let name = claims
+        .sub
+        .or(claims.client_id)
+        .unwrap_or_else(|| "oauth".into());
+    Ok(Principal::new(name, tools_for(cfg, &granted)))
+}
+
+/// Tools granted by `scopes`; `None` means every tool.
+fn tools_for(cfg: &OAuthConfig, scopes: &[String]) -> Option<Vec<String>> {
+    if cfg.scopes.is_empty() {
+        return None;
+    }
+    let mut tools = BTreeSet::new();
+    for tool in scopes.iter().filter_map(|s| cfg.scopes.get(s)).flatten() {
+        if tool == "*" {
+            return None;
+        }
+        tools.insert(tool.clone());
+    }
+    Some(tools.into_iter().collect())
+}
+
+/// Where clients find our metadata: the well-known path on the resource's origin.
+pub fn metadata_url(cfg: &OAuthConfig) -> String {
+    let origin = reqwest::Url::parse(&cfg.resource)
+        .map(|u| u.origin().ascii_serialization())
+        .unwrap_or_else(|_| cfg.resource.trim_end_matches('/').to_string());
+    format!("{origin}{METADATA_PATH}")
+}
+
+/// `GET /.well-known/oauth-protected-resource`; 404 while OAuth is off.
+pub async fn metadata(live: LiveRegistry) -> Response {
+    let cfg = live.config();
+    let Some(oauth) = &cfg.oauth else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    Json(json!({
+        "resource": oauth.resource,
+        "authorization_servers": [oauth.issuer],
+        "scopes_supported": oauth.scopes.keys().collect::<Vec<_>>(),
+        "bearer_methods_supported": ["header"],
+    }))
+    .into_response()
+}
+
+type CachedJwks = (PathBuf, Option<SystemTime>, Arc<JwkSet>);
+
+/// Parse the JWKS at `path`, re-reading it only when the file changes.
+pub fn load_jwks(path: &Path) -> Result<Arc<JwkSet>, String> {
+    static CACHE: Mutex<Option<CachedJwks>> = Mutex::new(None);
+
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let mut cache = CACHE.lock().unwrap();
+    if let Some((cached_path, cached_at, jwks)) = cache.as_ref() {
+        if cached_path == path && *cached_at == modified && modified.is_some() {
+            return Ok(jwks.clone());
+        }
+    }
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    let jwks: JwkSet = serde_json::from_str(&text)
+        .map_err(|e| format!("invalid JWKS in {}: {e}", path.display()))?;
+    let jwks = Arc::new(jwks);
+    *cache = Some((path.to_path_buf(), modified, jwks.clone()));
+    Ok(jwks)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+    use std::collections::BTreeMap;
+
+    pub(crate) const SECRET: &[u8] = b"irish-mcp-gateway-test-signing-key";
+    /// `SECRET`, base64url-encoded for the JWKS `k` member.
+    const SECRET_B64: &str = "aXJpc2gtbWNwLWdhdGV3YXktdGVzdC1zaWduaW5nLWtleQ";
+
+    /// An OAuth config backed by a one-key HS256 JWKS written to a temp file.
+    pub(crate) fn oauth_config(name: &str) -> OAuthConfig {
+        let path =
+            std::env::temp_dir().join(format!("irish-mcp-jwks-{}-{name}.json", std::process::id()));
+        let jwks =
+            json!({"keys": [{"kty": "oct", "kid": "test", "alg": "HS256", "k": SECRET_B64}]});
+        std::fs::write(&path, jwks.to_string()).unwrap();
+        OAuthConfig {
+            resource: "https://mcp.example.ie/mcp".into(),
+            issuer: "https://auth.example.ie".into(),
+            jwks_path: path.display().to_string(),
+            leeway_secs: 0,
+            scopes: BTreeMap::from([
+                ("mcp:echo".to_string(), vec!["hello.echo".to_string()]),
+                ("mcp:all".to_string(), vec!["*".to_string()]),
+            ]),
+        }
+    }
+
+    pub(crate) fn token(claims: serde_json::Value) -> String {
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("test".into());
+        encode(&header, &claims, &EncodingKey::from_secret(SECRET)).unwrap()
+    }
+
+    pub(crate) fn claims(scope: &str, exp_offset: i64) -> serde_json::Value {
+        json!({
+            "iss": "https://auth.example.ie",
+            "aud": "https://mcp.example.ie/mcp",
+            "sub": "aoife",
+            "scope": scope,
+            "exp": chrono::Utc::now().timestamp() + exp_offset,
+        })
+    }
+
+    #[test]
+    fn valid_token_maps_scopes_to_tools() {
+        let cfg = oauth_config("valid");
+        let p = verify(&cfg, &token(claims("openid mcp:echo", 300))).unwrap();
+        assert_eq!(p.name, "aoife");
+        assert!(p.allows("hello.echo"));
+        assert!(!p.allows("gael.grammar_check"));
+
+        let p = verify(&cfg, &token(claims("mcp:all", 300))).unwrap();
+        assert!(p.allows("gael.grammar_check"));
+
+        let p = verify(&cfg, &token(claims("openid", 300))).unwrap();
+        assert!(!p.allows("hello.echo"));
+    }
+
+    #[test]
+    fn it_rejects_expired_foreign_and_forged_tokens() {
+        let cfg = oauth_config("reject");
+        let expect = |token: String, reason: &str| {
+            let err = verify(&cfg, &token).unwrap_err();
+            assert!(matches!(err, GatewayError::Unauthorized(_)));
+            assert!(err.to_string().contains(reason), "{err}");
+        };
+        expect(token(claims("mcp:echo", -120)), "expired");
+
+        let mut wrong_aud = claims("mcp:echo", 300);
+        wrong_aud["aud"] = "https://elsewhere.example".into();
+        expect(token(wrong_aud), "audience");
+
+        let mut wrong_iss = claims("mcp:echo", 300);
+        wrong_iss["iss"] = "https://evil.example".into();
+        expect(token(wrong_iss), "issuer");
+
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("test".into());
+        let forged = encode(
+            &header,
+            &claims("mcp:all", 300),
+            &EncodingKey::from_secret(b"not the key"),
+        )
+        .unwrap();
+        expect(forged, "invalid token");
+        expect("not-a-jwt".into(), "invalid token");
+    }
+
+    #[test]
+    fn metadata_url_uses_resource_origin() {
+        let cfg = oauth_config("metadata");
+        assert_eq!(
+            metadata_url(&cfg),
+            "https://mcp.example.ie/.well-known/oauth-protected-resource"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Per-request span and correlation id for the HTTP app.
+//!
+//! Every request gets an `http.request` span, parented on the caller's
+//! `traceparent` when there is one, and a request id: the caller's
+//! `x-request-id` if usable, else a fresh one. The id is echoed back in the
+//! response and handed to MCP handlers through [`Correlation`].
+
+use axum::extract::Request;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::field::Empty;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::infra::http::headers::generate_request_id;
+use crate::infra::telemetry;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest inbound request id we pass on.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// The request's id and span, kept in request extensions so MCP handlers
+/// (which rmcp runs on their own tasks) can join the request's trace.
+#[derive(Clone, Debug)]
+pub struct Correlation {
+    pub request_id: String,
+    pub span: tracing::Span,
+}
+
+pub async fn trace_request(mut req: Request, next: Next) -> Response {
+    let request_id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|id| usable_request_id(id))
+        .map(String::from)
+        .unwrap_or_else(generate_request_id);
+
+    let span = tracing::info_span!(
+        "http.request",
+        otel.kind = "server",
+        http.request.method = %req.method(),
+        url.path = %req.uri().path(),
+        request_id = %request_id,
+        http.response.status_code = Empty,
+    );
+    span.set_parent(telemetry::extract(req.headers()));
+    req.extensions_mut().insert(Correlation {
+        request_id: request_id.clone(),
+        span: span.clone(),
+    });
+
+    let mut resp = telemetry::with_request_id(request_id.clone(), next.run(req))
+        .instrument(span.clone())
+        .await;
+    span.record("http.response.status_code", resp.status().as_u16());
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        resp.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    resp
+}
+
+fn usable_request_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN && id.bytes().all(|b| b.is_ascii_graphic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/id",
+                get(|| async { telemetry::current_request_id().unwrap_or_default() }),
+            )
+            .layer(axum::middleware::from_fn(trace_request))
+    }
+
+    async fn call(request_id: Option<&str>) -> (String, String) {
+        let mut req = Request::builder().uri("/id");
+        if let Some(id) = request_id {
+            req = req.header(REQUEST_ID_HEADER, id);
+        }
+        let resp = app()
+            .oneshot(req.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let echoed = resp.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = axum::body::to_bytes(resp.into_body(), 1024).await.unwrap();
+        (echoed, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn it_keeps_and_echoes_the_callers_request_id() {
+        let (echoed, seen) = call(Some("abc-123")).await;
+        assert_eq!(echoed, "abc-123");
+        assert_eq!(seen, "abc-123");
+    }
+
+    #[tokio::test]
+    async fn it_generates_an_id_when_missing_or_unusable() {
+        let (echoed, seen) = call(None).await;
+        assert!(echoed.starts_with("gw-"), "{echoed}");
+        assert_eq!(echoed, seen);
+
+        let (echoed, _) = call(Some(&"x".repeat(500))).await;
+        assert!(echoed.starts_with("gw-"), "{echoed}");
+    }
+}
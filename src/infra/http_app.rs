@@ -1,15 +1,25 @@
 use axum::{
-    routing::{any_service, get, post},
+    http::header,
+    middleware::{from_fn, from_fn_with_state},
+    routing::{any_service, get, post, MethodRouter},
     Json, Router,
 };
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
 use serde_json::{json, Value};
 use std::sync::Arc;
 
+use crate::infra::http::{auth, oauth, trace};
+use crate::infra::metrics;
+use crate::infra::runtime::breaker::{BreakerState, CircuitBreaker};
 use crate::infra::runtime::mcp_transport;
-use crate::tools::registry::Registry;
+use crate::infra::runtime::queue::UpstreamQueue;
+use crate::infra::runtime::rate_limit::{self, RateLimiter};
+use crate::tools::registry::live::LiveRegistry;
+use crate::tools::registry::tool_router::registry_factory;
 
 /// Enhanced health check endpoint with service status
-async fn health_check() -> Json<Value> {
+async fn health_check(live: LiveRegistry) -> Json<Value> {
+    let cfg = live.config();
     let mut status = json!({
         "status": "healthy",
         "timestamp": chrono::Utc::now().to_rfc3339(),
@@ -17,105 +27,137 @@ async fn health_check() -> Json<Value> {
         "services": {}
     });
 
-    // Check grammar service if configured
-    if let Ok(grammar_url) = std::env::var("GRAMADOIR_BASE_URL") {
-        if !grammar_url.is_empty() {
-            let client = crate::clients::gramadoir::GramadoirRemote::new(grammar_url);
-            match client.analyze("test").await {
-                Ok(_) => {
-                    status["services"]["grammar"] = json!({
-                        "status": "healthy",
-                        "url": std::env::var("GRAMADOIR_BASE_URL").unwrap_or_default()
-                    });
-                }
-                Err(_) => {
-                    status["services"]["grammar"] = json!({
-                        "status": "unhealthy",
-                        "url": std::env::var("GRAMADOIR_BASE_URL").unwrap_or_default()
-                    });
-                    status["status"] = json!("degraded");
-                }
+    // Grammar health follows its circuit breaker; probing Gramadóir from here
+    // would count towards the breaker and take queue slots from user traffic
+    if let Some(grammar_url) = &cfg.gramadoir.base_url {
+        let healthy =
+            CircuitBreaker::for_upstream(grammar_url).snapshot().state == BreakerState::Closed;
+        status["services"]["grammar"] = json!({
+            "status": if healthy { "healthy" } else { "unhealthy" },
+            "url": grammar_url
+        });
+    }
+
+    // Check spellcheck tool health via registry if configured
+    if let Some(spell_url) = &cfg.spellcheck.base_url {
+        if let Some(tool) = live.registry().0.get("gael.spellcheck.v1") {
+            if tool.health().await {
+                status["services"]["spellcheck"] = json!({
+                    "status": "healthy",
+                    "url": spell_url
+                });
+            } else {
+                status["services"]["spellcheck"] = json!({
+                    "status": "unhealthy",
+                    "url": spell_url
+                });
+                status["status"] = json!("degraded");
             }
         }
     }
 
-    // Check spellcheck tool health via registry if configured
-    if let Ok(spell_url) = std::env::var("SPELLCHECK_BASE_URL") {
-        if !spell_url.is_empty() {
-            let reg = crate::tools::registry::build_registry();
-            if let Some(tool) = reg.0.get("gael.spellcheck.v1") {
-                if tool.health().await {
-                    status["services"]["spellcheck"] = json!({
-                        "status": "healthy",
-                        "url": spell_url
-                    });
-                } else {
-                    status["services"]["spellcheck"] = json!({
-                        "status": "unhealthy",
-                        "url": spell_url
-                    });
-                    status["status"] = json!("degraded");
-                }
+    // Report circuit breaker state and queue depth for each configured upstream
+    for (service, upstream) in [("grammar", &cfg.gramadoir), ("spellcheck", &cfg.spellcheck)] {
+        if let Some(url) = &upstream.base_url {
+            let breaker = CircuitBreaker::for_upstream(url).snapshot();
+            if breaker.state != BreakerState::Closed {
+                status["status"] = json!("degraded");
             }
+            status["services"][service]["breaker"] = json!(breaker);
+            status["services"][service]["queue"] =
+                json!(UpstreamQueue::for_upstream(url).snapshot());
         }
     }
 
+    if let Some(cache) = live.cache() {
+        status["cache"] = json!(cache.stats());
+    }
+
     Json(status)
 }
 
-/// Default, spec-compliant app: `/healthz` + streamable MCP at `/mcp`.
-pub fn build_app_default() -> Router {
-    let session_mgr = Arc::new(
-        rmcp::transport::streamable_http_server::session::local::LocalSessionManager::default(),
+fn health_route<S: Clone + Send + Sync + 'static>(live: &LiveRegistry) -> MethodRouter<S> {
+    let live = live.clone();
+    get(move || health_check(live.clone()))
+}
+
+fn metrics_route<S: Clone + Send + Sync + 'static>(
+    live: &LiveRegistry,
+    sessions: &Arc<LocalSessionManager>,
+) -> MethodRouter<S> {
+    let (live, sessions) = (live.clone(), sessions.clone());
+    get(move || {
+        let (live, sessions) = (live.clone(), sessions.clone());
+        async move {
+            let body = metrics::render(&live, &sessions).await;
+            ([(header::CONTENT_TYPE, metrics::CONTENT_TYPE)], body)
+        }
+    })
+}
+
+fn oauth_metadata_route<S: Clone + Send + Sync + 'static>(live: &LiveRegistry) -> MethodRouter<S> {
+    let live = live.clone();
+    get(move || oauth::metadata(live.clone()))
+}
+
+/// Default, spec-compliant app: `/healthz`, `/metrics` + streamable MCP at `/mcp`.
+pub fn build_app_default(live: &LiveRegistry) -> Router {
+    let session_mgr = Arc::new(LocalSessionManager::default());
+    let mcp_service = mcp_transport::make_streamable_http_handler_service(
+        registry_factory(live.clone()),
+        session_mgr.clone(),
     );
-    let factory = || {
-        let base = std::env::var("GRAMADOIR_BASE_URL").unwrap_or_default();
-        let handler = crate::tools::grammar::tool_router::GrammarSvc {
-            checker: crate::clients::gramadoir::GramadoirRemote::new(base),
-        };
-        let tools = crate::tools::grammar::tool_router::GrammarSvc::router();
-        (handler, tools)
-    };
-    let mcp_service = mcp_transport::make_streamable_http_service(factory, session_mgr);
 
     Router::new()
-        .route("/healthz", get(health_check))
         .route_service("/mcp", any_service(mcp_service))
+        .route_layer(from_fn_with_state(
+            (live.clone(), Arc::new(RateLimiter::new())),
+            rate_limit::enforce,
+        ))
+        // Credentials guard the routes above; health, metrics and OAuth metadata stay open
+        .route_layer(from_fn_with_state(live.clone(), auth::require_auth))
+        .route("/healthz", health_route(live))
+        .route("/metrics", metrics_route(live, &session_mgr))
+        .route(oauth::METADATA_PATH, oauth_metadata_route(live))
+        // Outermost, so rejected requests get a span and request id too
+        .layer(from_fn(trace::trace_request))
 }
 
 /// Spec app **plus** deprecated demo REST route at `/v1/grammar/check`.
-pub fn build_app_with_deprecated_api(registry: Registry) -> Router {
-    let session_mgr = Arc::new(
-        rmcp::transport::streamable_http_server::session::local::LocalSessionManager::default(),
+pub fn build_app_with_deprecated_api(live: &LiveRegistry) -> Router {
+    let session_mgr = Arc::new(LocalSessionManager::default());
+    let mcp_service = mcp_transport::make_streamable_http_handler_service(
+        registry_factory(live.clone()),
+        session_mgr.clone(),
     );
-    let factory = || {
-        let base = std::env::var("GRAMADOIR_BASE_URL").unwrap_or_default();
-        let handler = crate::tools::grammar::tool_router::GrammarSvc {
-            checker: crate::clients::gramadoir::GramadoirRemote::new(base),
-        };
-        let tools = crate::tools::grammar::tool_router::GrammarSvc::router();
-        (handler, tools)
-    };
-    let mcp_service = mcp_transport::make_streamable_http_service(factory, session_mgr);
 
     Router::new()
-        .route("/healthz", get(health_check))
         .route_service("/mcp", any_service(mcp_service))
         .route("/v1/grammar/check", post(crate::api::mcp::http))
-        .with_state(registry)
+        .route_layer(from_fn_with_state(
+            (live.clone(), Arc::new(RateLimiter::new())),
+            rate_limit::enforce,
+        ))
+        // Credentials guard the routes above; health, metrics and OAuth metadata stay open
+        .route_layer(from_fn_with_state(live.clone(), auth::require_auth))
+        .route("/healthz", health_route(live))
+        .route("/metrics", metrics_route(live, &session_mgr))
+        .route(oauth::METADATA_PATH, oauth_metadata_route(live))
+        .with_state(live.clone())
+        .layer(from_fn(trace::trace_request))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::infra::config::Config;
     use axum::http::{Request, StatusCode};
     use httpmock::prelude::*;
-    use serial_test::serial;
     use tower::ServiceExt;
 
     #[tokio::test]
     async fn healthz_responds_ok_on_default_app() {
-        let app = build_app_default();
+        let app = build_app_default(&LiveRegistry::new(Config::default()));
         let req = axum::http::Request::builder()
             .method("GET")
             .uri("/healthz")
@@ -134,11 +176,13 @@ mod tests {
         ));
         assert!(json["timestamp"].is_string());
         assert!(json["version"].is_string());
+        assert!(json["cache"]["hits"].is_u64());
+        assert!(json["cache"]["misses"].is_u64());
     }
 
     #[tokio::test]
     async fn healthz_returns_structured_response() {
-        let app = build_app_default();
+        let app = build_app_default(&LiveRegistry::new(Config::default()));
         let req = axum::http::Request::builder()
             .method("GET")
             .uri("/healthz")
@@ -157,16 +201,53 @@ mod tests {
     }
 
     #[tokio::test]
-    #[serial]
+    async fn metrics_exposes_rest_calls_in_prometheus_format() {
+        let app = build_app_with_deprecated_api(&LiveRegistry::new(Config::default()));
+        let call = Request::builder()
+            .method("POST")
+            .uri("/v1/grammar/check")
+            .header("content-type", "application/json")
+            .body(axum::body::Body::from(
+                r#"{"jsonrpc":"2.0","id":1,"method":"tools.call","params":{"name":"hello.echo","arguments":{"name":"Niamh"}}}"#,
+            ))
+            .unwrap();
+        assert_eq!(
+            app.clone().oneshot(call).await.unwrap().status(),
+            StatusCode::OK
+        );
+
+        let req = Request::builder()
+            .uri("/metrics")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain; version=0.0.4"));
+        let body = axum::body::to_bytes(resp.into_body(), 1 << 20)
+            .await
+            .unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text.contains(
+            r#"gateway_mcp_requests_total{method="tools/call",outcome="ok",tool="hello.echo"}"#
+        ));
+        assert!(text.contains("gateway_mcp_sessions_active 0"));
+        assert!(text.contains("gateway_cache_hits_total"));
+    }
+
+    #[tokio::test]
     async fn healthz_indicates_grammar_healthy() {
         let server = MockServer::start();
-        server.mock(|when, then| {
-            when.method(POST).path("/api/gramadoir/1.0");
+        let probe = server.mock(|when, then| {
+            when.method(POST).path("/healthz-healthy/api/gramadoir/1.0");
             then.status(200).json_body(serde_json::json!([]));
         });
 
-        std::env::set_var("GRAMADOIR_BASE_URL", server.base_url());
-        let app = build_app_default();
+        let mut cfg = Config::default();
+        cfg.gramadoir.base_url = Some(server.url("/healthz-healthy"));
+        let app = build_app_default(&LiveRegistry::new(cfg));
         let req = Request::builder()
             .method("GET")
             .uri("/healthz")
@@ -176,20 +257,27 @@ mod tests {
         let body = axum::body::to_bytes(resp.into_body(), 1024).await.unwrap();
         let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
         assert_eq!(json["services"]["grammar"]["status"], "healthy");
-        std::env::remove_var("GRAMADOIR_BASE_URL");
+        // Health checks never call the upstream themselves.
+        probe.assert_hits(0);
     }
 
     #[tokio::test]
-    #[serial]
     async fn healthz_indicates_grammar_unhealthy() {
-        let server = MockServer::start();
-        server.mock(|when, then| {
-            when.method(POST).path("/api/gramadoir/1.0");
-            then.status(500).body("boom");
-        });
+        use crate::infra::runtime::limits::AttemptError;
+
+        // Breakers are shared per base URL, so give this upstream one of its own.
+        let url = "http://healthz-unhealthy.invalid";
+        let breaker = CircuitBreaker::for_upstream(url);
+        breaker.configure(1, std::time::Duration::from_secs(60));
+        let _ = breaker
+            .acquire()
+            .unwrap()
+            .run(async { Err::<(), _>(AttemptError::Unavailable("connect refused".into())) })
+            .await;
 
-        std::env::set_var("GRAMADOIR_BASE_URL", server.base_url());
-        let app = build_app_default();
+        let mut cfg = Config::default();
+        cfg.gramadoir.base_url = Some(url.into());
+        let app = build_app_default(&LiveRegistry::new(cfg));
         let req = Request::builder()
             .method("GET")
             .uri("/healthz")
@@ -199,16 +287,21 @@ mod tests {
         let body = axum::body::to_bytes(resp.into_body(), 1024).await.unwrap();
         let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
         assert_eq!(json["services"]["grammar"]["status"], "unhealthy");
+        assert_eq!(json["services"]["grammar"]["breaker"]["state"], "open");
         assert_eq!(json["status"], "degraded");
-        std::env::remove_var("GRAMADOIR_BASE_URL");
     }
 
     #[tokio::test]
     async fn deprecated_route_handles_grammar_check_when_configured() {
-        // Configure env so registry includes grammar tool
-        std::env::set_var("GRAMADOIR_BASE_URL", "http://example");
-        let reg = crate::tools::registry::build_registry();
-        let app = build_app_with_deprecated_api(reg);
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/api/gramadoir/1.0");
+            then.status(200).json_body(serde_json::json!([]));
+        });
+        // Configure Gramadóir so the registry includes the grammar tool
+        let mut cfg = Config::default();
+        cfg.gramadoir.base_url = Some(server.base_url());
+        let app = build_app_with_deprecated_api(&LiveRegistry::new(cfg));
 
         let body = r#"{"jsonrpc":"2.0","id":2,"method":"tools.call","params":{"name":"gael.grammar_check","arguments":{"text":"Tá an peann ar an mbord"}}}"#;
         let req = Request::builder()
@@ -223,8 +316,8 @@ mod tests {
 
     #[tokio::test]
     async fn deprecated_route_returns_error_on_unknown_tool() {
-        let reg = crate::tools::registry::build_registry();
-        let app = build_app_with_deprecated_api(reg);
+        let cfg = Config::default();
+        let app = build_app_with_deprecated_api(&LiveRegistry::new(cfg));
 
         let body = r#"{"jsonrpc":"2.0","id":99,"method":"tools.call","params":{"name":"does.not.exist","arguments":{}}}"#;
         let req = Request::builder()
@@ -234,14 +327,76 @@ mod tests {
             .body(axum::body::Body::from(body))
             .unwrap();
         let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
         let bytes = axum::body::to_bytes(resp.into_body(), 1024).await.unwrap();
         let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
-        assert_eq!(json["error"]["code"], -32000);
+        assert_eq!(json["error"]["code"], -32002);
+    }
+
+    #[tokio::test]
+    async fn request_id_and_trace_context_reach_the_upstream() {
+        let (subscriber, provider, exporter) = crate::infra::telemetry::tests::recording();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let server = MockServer::start();
+        let upstream = server.mock(|when, then| {
+            when.method(POST)
+                .path("/api/gramadoir/1.0")
+                .header("x-request-id", "trace-e2e-1")
+                .header_exists("traceparent");
+            then.status(200).json_body(serde_json::json!([]));
+        });
+        let mut cfg = Config::default();
+        cfg.gramadoir.base_url = Some(server.base_url());
+        let app = build_app_with_deprecated_api(&LiveRegistry::new(cfg));
+
+        let body = r#"{"jsonrpc":"2.0","id":3,"method":"tools.call","params":{"name":"gael.grammar_check","arguments":{"text":"Rianú ó cheann ceann"}}}"#;
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/grammar/check")
+            .header("content-type", "application/json")
+            .header("x-request-id", "trace-e2e-1")
+            .header(
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            )
+            .body(axum::body::Body::from(body))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert!(resp.status().is_success());
+        assert_eq!(resp.headers()["x-request-id"], "trace-e2e-1");
+        upstream.assert();
+
+        provider.force_flush().unwrap();
+        let spans = exporter.get_finished_spans().unwrap();
+        let span = |name: &str| {
+            spans
+                .iter()
+                .find(|s| s.name == name)
+                .unwrap_or_else(|| panic!("no {name} span"))
+        };
+        let chain = ["http.request", "mcp.request", "tool.call", "upstream.call"];
+        for pair in chain.windows(2) {
+            let (parent, child) = (span(pair[0]), span(pair[1]));
+            assert_eq!(
+                child.parent_span_id,
+                parent.span_context.span_id(),
+                "{} should be a child of {}",
+                pair[1],
+                pair[0]
+            );
+        }
+        for s in &spans {
+            assert_eq!(
+                s.span_context.trace_id().to_string(),
+                "4bf92f3577b34da6a3ce929d0e0e4736"
+            );
+        }
     }
 
     #[tokio::test]
     async fn healthz_json_shape_has_required_fields() {
-        let app = build_app_default();
+        let app = build_app_default(&LiveRegistry::new(Config::default()));
         let req = axum::http::Request::builder()
             .method("GET")
             .uri("/healthz")
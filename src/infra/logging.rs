@@ -1,3 +1,118 @@
+//! Log output: text or JSON lines, filtered per module, with users' text kept
+//! out of the logs unless the config says otherwise.
+//!
+//! Anything that may carry what a user wrote (tool arguments, tool output)
+//! goes through [`scrub`] before it is logged.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+use crate::infra::config::Config;
+use crate::infra::telemetry;
+
+/// JSON keys whose values are user text or tool output.
+const USER_DATA_KEYS: [&str; 3] = ["text", "texts", "result"];
+
+/// How users' text appears in logs (`logging.user_text`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextPolicy {
+    /// Only the length: `[redacted 42 chars]`.
+    Redact,
+    /// A short SHA-256 prefix and the length, so repeats can be matched up.
+    Hash,
+    /// Logged as is.
+    Full,
+}
+
+static TEXT_POLICY: AtomicU8 = AtomicU8::new(TextPolicy::Redact as u8);
+
+impl TextPolicy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "redact" => Some(Self::Redact),
+            "hash" => Some(Self::Hash),
+            "full" => Some(Self::Full),
+            _ => None,
+        }
+    }
+
+    /// The policy in force; `redact` until the server sets one.
+    pub fn current() -> Self {
+        match TEXT_POLICY.load(Ordering::Relaxed) {
+            1 => Self::Hash,
+            2 => Self::Full,
+            _ => Self::Redact,
+        }
+    }
+
+    fn install(self) {
+        TEXT_POLICY.store(self as u8, Ordering::Relaxed);
+    }
+
+    pub fn apply(self, text: &str) -> String {
+        let chars = text.chars().count();
+        match self {
+            Self::Redact => format!("[redacted {chars} chars]"),
+            Self::Hash => {
+                let digest = Sha256::digest(text.as_bytes());
+                let hex: String = digest[..6].iter().map(|b| format!("{b:02x}")).collect();
+                format!("[sha256:{hex} {chars} chars]")
+            }
+            Self::Full => text.to_string(),
+        }
+    }
+
+    /// `value` with the strings under user-data keys (`text`, `texts`,
+    /// `result`) passed through [`apply`](Self::apply); structure and
+    /// everything else are kept.
+    pub fn scrub(self, value: &Value) -> Value {
+        if self == Self::Full {
+            return value.clone();
+        }
+        match value {
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| {
+                        let v = if USER_DATA_KEYS.contains(&k.as_str()) {
+                            self.scrub_all(v)
+                        } else {
+                            self.scrub(v)
+                        };
+                        (k.clone(), v)
+                    })
+                    .collect(),
+            ),
+            Value::Array(items) => Value::Array(items.iter().map(|v| self.scrub(v)).collect()),
+            other => other.clone(),
+        }
+    }
+
+    fn scrub_all(self, value: &Value) -> Value {
+        match value {
+            Value::String(s) => Value::String(self.apply(s)),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), self.scrub_all(v)))
+                    .collect(),
+            ),
+            Value::Array(items) => Value::Array(items.iter().map(|v| self.scrub_all(v)).collect()),
+            other => other.clone(),
+        }
+    }
+}
+
+/// `value` as JSON, with user text handled per the current policy.
+pub fn scrub(value: &impl Serialize) -> Value {
+    TextPolicy::current().scrub(&serde_json::to_value(value).unwrap_or_default())
+}
+
 pub fn init() {
     // Initialize tracing subscriber once, honoring RUST_LOG if set.
     // Default to info level; allow override via RUST_LOG (e.g., "debug").
@@ -8,11 +123,119 @@ pub fn init() {
         .try_init();
 }
 
+/// Flushes and shuts down the trace exporter when dropped.
+pub struct TracingGuard(SdkTracerProvider);
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.0.shutdown() {
+            eprintln!("trace exporter shutdown failed: {e}");
+        }
+    }
+}
+
+/// Server logging as `cfg.logging` describes, plus spans exported per
+/// `cfg.tracing`. `RUST_LOG`, when set, overrides the configured levels.
+/// Keep the guard alive until exit so buffered spans are flushed.
+pub fn init_server(cfg: &Config) -> Result<TracingGuard, String> {
+    let policy = TextPolicy::from_name(&cfg.logging.user_text)
+        .ok_or_else(|| format!("unknown user_text policy '{}'", cfg.logging.user_text))?;
+    let directives = std::env::var("RUST_LOG").unwrap_or_else(|_| cfg.logging.directives());
+    let filter = EnvFilter::try_new(&directives)
+        .map_err(|e| format!("invalid log levels '{directives}': {e}"))?;
+    let json = match cfg.logging.format.as_str() {
+        "text" => false,
+        "json" => true,
+        other => return Err(format!("unknown log format '{other}'")),
+    };
+    // Stdout carries the JSON-RPC stream in stdio mode
+    if cfg.mode == "stdio" && cfg.tracing.exporter == "stdout" {
+        return Err("the stdout trace exporter cannot be used in stdio mode".into());
+    }
+    let provider = telemetry::tracer_provider(&cfg.tracing)?;
+
+    policy.install();
+    // Span fields (request ids among them) ride along on every JSON line
+    let json_layer = json.then(|| {
+        tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(false)
+    });
+    let text_layer = (!json).then(|| tracing_subscriber::fmt::layer().with_target(false));
+    let _ = tracing_subscriber::registry()
+        .with(filter)
+        .with(json_layer)
+        .with(text_layer)
+        .with(telemetry::layer(&provider))
+        .try_init();
+    Ok(TracingGuard(provider))
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use serde_json::json;
+
     #[test]
     fn init_is_idempotent() {
         super::init();
         super::init();
     }
+
+    #[test]
+    fn init_server_rejects_bad_settings() {
+        let mut cfg = Config::default();
+        cfg.tracing.exporter = "jaeger".into();
+        assert!(init_server(&cfg).is_err());
+
+        let mut cfg = Config::default();
+        cfg.logging.format = "logfmt".into();
+        assert!(init_server(&cfg).is_err());
+
+        let mut cfg = Config {
+            mode: "stdio".into(),
+            ..Config::default()
+        };
+        cfg.tracing.exporter = "stdout".into();
+        assert!(init_server(&cfg).is_err());
+    }
+
+    #[test]
+    fn policies_render_text() {
+        assert_eq!(
+            TextPolicy::Redact.apply("Tá sé fuar"),
+            "[redacted 10 chars]"
+        );
+        let hashed = TextPolicy::Hash.apply("Tá sé fuar");
+        assert!(hashed.starts_with("[sha256:"), "{hashed}");
+        assert!(hashed.ends_with(" 10 chars]"), "{hashed}");
+        assert_eq!(hashed, TextPolicy::Hash.apply("Tá sé fuar"));
+        assert_ne!(hashed, TextPolicy::Hash.apply("Tá sé te"));
+        assert_eq!(TextPolicy::Full.apply("Tá sé fuar"), "Tá sé fuar");
+        assert_eq!(TextPolicy::current(), TextPolicy::Redact);
+    }
+
+    #[test]
+    fn scrub_hides_arguments_and_tool_output() {
+        let call = json!({
+            "name": "gael.grammar_check",
+            "arguments": { "text": "mo théacs príobháideach", "texts": ["a", "bc"] },
+        });
+        let scrubbed = TextPolicy::Redact.scrub(&call);
+        assert_eq!(scrubbed["name"], "gael.grammar_check");
+        assert_eq!(scrubbed["arguments"]["text"], "[redacted 23 chars]");
+        assert_eq!(scrubbed["arguments"]["texts"][1], "[redacted 2 chars]");
+
+        let resp = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": { "issues": [{ "code": "URU", "context": "ar an mbord", "start": 3 }] },
+        });
+        let scrubbed = TextPolicy::Hash.scrub(&resp);
+        assert_eq!(scrubbed["jsonrpc"], "2.0");
+        assert_eq!(scrubbed["result"]["issues"][0]["start"], 3);
+        assert!(!scrubbed.to_string().contains("mbord"));
+        assert_eq!(TextPolicy::Full.scrub(&resp), resp);
+    }
 }
@@ -0,0 +1,255 @@
+//! Prometheus metrics, served at `GET /metrics`.
+//!
+//! Request and upstream counters/histograms are recorded as calls happen.
+//! Session, cache, breaker and queue figures are read from their owners at
+//! scrape time, so nothing has to keep them in sync.
+
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+
+use crate::core::error::GatewayError;
+use crate::infra::config::Config;
+use crate::infra::runtime::breaker::{BreakerState, CircuitBreaker};
+use crate::infra::runtime::cache::ResultCache;
+use crate::infra::runtime::queue::UpstreamQueue;
+use crate::tools::registry::live::LiveRegistry;
+
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+pub struct Metrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    request_seconds: HistogramVec,
+    upstream_requests: IntCounterVec,
+    upstream_seconds: HistogramVec,
+}
+
+/// Process-wide metrics.
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::new)
+}
+
+/// `"ok"`, or the error's kind, as an outcome label.
+pub fn outcome<T>(result: &Result<T, GatewayError>) -> &'static str {
+    match result {
+        Ok(_) => "ok",
+        Err(e) => e.kind(),
+    }
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let requests = IntCounterVec::new(
+            Opts::new(
+                "gateway_mcp_requests_total",
+                "MCP requests handled, by method, tool and outcome.",
+            ),
+            &["method", "tool", "outcome"],
+        )
+        .expect("valid metric");
+        let request_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "gateway_mcp_request_duration_seconds",
+                "Time spent handling MCP requests, by method and tool.",
+            ),
+            &["method", "tool"],
+        )
+        .expect("valid metric");
+        let upstream_requests = IntCounterVec::new(
+            Opts::new(
+                "gateway_upstream_requests_total",
+                "Calls to upstream services, including retries, by outcome.",
+            ),
+            &["upstream", "outcome"],
+        )
+        .expect("valid metric");
+        let upstream_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "gateway_upstream_request_duration_seconds",
+                "Time spent on upstream calls, including retries.",
+            ),
+            &["upstream"],
+        )
+        .expect("valid metric");
+
+        let registry = Registry::new();
+        for collector in [
+            Box::new(requests.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(request_seconds.clone()),
+            Box::new(upstream_requests.clone()),
+            Box::new(upstream_seconds.clone()),
+        ] {
+            registry.register(collector).expect("unique metric");
+        }
+        Self {
+            registry,
+            requests,
+            request_seconds,
+            upstream_requests,
+            upstream_seconds,
+        }
+    }
+
+    /// Record one MCP request. `method` uses the slash form (`tools/call`);
+    /// `tool` is empty for methods that do not name one.
+    pub fn observe_request(&self, method: &str, tool: &str, outcome: &str, elapsed: Duration) {
+        self.requests
+            .with_label_values(&[method, tool, outcome])
+            .inc();
+        self.request_seconds
+            .with_label_values(&[method, tool])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Record one upstream call (`gramadoir` or `spellcheck`).
+    pub fn observe_upstream(&self, upstream: &str, outcome: &str, elapsed: Duration) {
+        self.upstream_requests
+            .with_label_values(&[upstream, outcome])
+            .inc();
+        self.upstream_seconds
+            .with_label_values(&[upstream])
+            .observe(elapsed.as_secs_f64());
+    }
+}
+
+/// The Prometheus text exposition of everything we track.
+pub async fn render(live: &LiveRegistry, sessions: &LocalSessionManager) -> String {
+    let cfg = live.config();
+    let scrape = Registry::new();
+    let active = IntGauge::new("gateway_mcp_sessions_active", "Open MCP HTTP sessions.")
+        .expect("valid metric");
+    active.set(sessions.sessions.read().await.len() as i64);
+    scrape.register(Box::new(active)).expect("unique metric");
+    register_cache(&scrape, live.cache());
+    register_upstreams(&scrape, &cfg);
+
+    let mut families = metrics().registry.gather();
+    families.extend(scrape.gather());
+    let mut out = Vec::new();
+    TextEncoder::new()
+        .encode(&families, &mut out)
+        .expect("text encoding");
+    String::from_utf8(out).expect("prometheus text is UTF-8")
+}
+
+fn register_cache(scrape: &Registry, cache: Option<Arc<ResultCache>>) {
+    let Some(cache) = cache else {
+        return;
+    };
+    let stats = cache.stats();
+    let entries = IntGauge::new("gateway_cache_entries", "Entries in the result cache.")
+        .expect("valid metric");
+    entries.set(stats.entries as i64);
+    let capacity =
+        IntGauge::new("gateway_cache_capacity", "Result cache capacity.").expect("valid metric");
+    capacity.set(stats.capacity as i64);
+    let hits =
+        IntCounter::new("gateway_cache_hits_total", "Result cache hits.").expect("valid metric");
+    hits.inc_by(stats.hits);
+    let misses = IntCounter::new("gateway_cache_misses_total", "Result cache misses.")
+        .expect("valid metric");
+    misses.inc_by(stats.misses);
+    for collector in [
+        Box::new(entries) as Box<dyn prometheus::core::Collector>,
+        Box::new(capacity),
+        Box::new(hits),
+        Box::new(misses),
+    ] {
+        scrape.register(collector).expect("unique metric");
+    }
+}
+
+fn register_upstreams(scrape: &Registry, cfg: &Config) {
+    let gauge = |name: &str, help: &str| {
+        let vec = IntGaugeVec::new(Opts::new(name, help), &["upstream"]).expect("valid metric");
+        scrape
+            .register(Box::new(vec.clone()))
+            .expect("unique metric");
+        vec
+    };
+    let state = gauge(
+        "gateway_breaker_state",
+        "Circuit breaker state: 0 closed, 1 half-open, 2 open.",
+    );
+    let failures = gauge(
+        "gateway_breaker_consecutive_failures",
+        "Consecutive upstream outages seen by the breaker.",
+    );
+    let in_flight = gauge(
+        "gateway_upstream_in_flight",
+        "Requests currently with the upstream.",
+    );
+    let queued = gauge(
+        "gateway_upstream_queued",
+        "Requests waiting for an upstream slot.",
+    );
+
+    for (name, upstream) in [
+        ("gramadoir", &cfg.gramadoir),
+        ("spellcheck", &cfg.spellcheck),
+    ] {
+        let Some(url) = &upstream.base_url else {
+            continue;
+        };
+        let breaker = CircuitBreaker::for_upstream(url).snapshot();
+        state.with_label_values(&[name]).set(match breaker.state {
+            BreakerState::Closed => 0,
+            BreakerState::HalfOpen => 1,
+            BreakerState::Open => 2,
+        });
+        failures
+            .with_label_values(&[name])
+            .set(breaker.consecutive_failures.into());
+        let queue = UpstreamQueue::for_upstream(url).snapshot();
+        in_flight
+            .with_label_values(&[name])
+            .set(queue.in_flight as i64);
+        queued.with_label_values(&[name]).set(queue.queued as i64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn it_renders_recorded_and_scraped_metrics() {
+        metrics().observe_request("tools/call", "hello.echo", "ok", Duration::from_millis(3));
+        metrics().observe_upstream("gramadoir", "timeout", Duration::from_secs(6));
+
+        let live = LiveRegistry::new(Config {
+            gramadoir: crate::infra::config::UpstreamConfig {
+                base_url: Some("http://metrics-test-gramadoir".into()),
+                ..Default::default()
+            },
+            ..Config::default()
+        });
+        let text = render(&live, &LocalSessionManager::default()).await;
+        assert!(text.contains(
+            r#"gateway_mcp_requests_total{method="tools/call",outcome="ok",tool="hello.echo"}"#
+        ));
+        assert!(text.contains(
+            r#"gateway_mcp_request_duration_seconds_bucket{method="tools/call",tool="hello.echo",le="0.005"}"#
+        ));
+        assert!(text.contains(
+            r#"gateway_upstream_requests_total{outcome="timeout",upstream="gramadoir"}"#
+        ));
+        assert!(text.contains("gateway_mcp_sessions_active 0"));
+        assert!(text.contains(r#"gateway_breaker_state{upstream="gramadoir"} 0"#));
+        assert!(text.contains(r#"gateway_upstream_queued{upstream="gramadoir"} 0"#));
+    }
+
+    #[test]
+    fn outcome_uses_error_kind() {
+        assert_eq!(outcome(&Ok::<_, GatewayError>(())), "ok");
+        let err: Result<(), _> = Err(GatewayError::NotFound("x".into()));
+        assert_eq!(outcome(&err), "not_found");
+    }
+}
@@ -1,10 +1,15 @@
 pub mod config;
 pub mod http_app;
 pub mod logging;
+pub mod metrics;
 pub mod http {
+    pub mod auth;
     pub mod headers;
     pub mod json;
+    pub mod oauth;
     pub mod sse;
+    pub mod trace;
 }
 pub mod boot;
 pub mod runtime;
+pub mod telemetry;
@@ -0,0 +1,347 @@
+//! Per-upstream circuit breakers.
+//!
+//! A breaker opens after `failure_threshold` consecutive outage failures
+//! (connect errors, timeouts, 5xx) and rejects calls for `open_for`. After
+//! that a single half-open probe is let through: success closes the breaker,
+//! failure re-opens it.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::core::error::GatewayError;
+use crate::infra::config::UpstreamConfig;
+use crate::infra::runtime::limits::AttemptError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BreakerState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BreakerSnapshot {
+    pub state: BreakerState,
+    pub consecutive_failures: u32,
+}
+
+pub struct CircuitBreaker {
+    upstream: String,
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    failure_threshold: u32,
+    open_for: Duration,
+    state: BreakerState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probing: bool,
+}
+
+impl CircuitBreaker {
+    pub fn new(upstream: impl Into<String>, failure_threshold: u32, open_for: Duration) -> Self {
+        Self {
+            upstream: upstream.into(),
+            inner: Mutex::new(Inner {
+                failure_threshold: failure_threshold.max(1),
+                open_for,
+                state: BreakerState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                probing: false,
+            }),
+        }
+    }
+
+    /// Process-wide breaker for `upstream` (its base URL). New breakers start with
+    /// the `UpstreamConfig` defaults; clients apply their settings via `configure`.
+    pub fn for_upstream(upstream: &str) -> Arc<CircuitBreaker> {
+        let mut breakers = registry().lock().expect("breaker registry lock");
+        breakers
+            .entry(upstream.to_string())
+            .or_insert_with(|| {
+                let defaults = UpstreamConfig::default();
+                Arc::new(CircuitBreaker::new(
+                    upstream,
+                    defaults.breaker_failure_threshold,
+                    defaults.breaker_open_for,
+                ))
+            })
+            .clone()
+    }
+
+    /// Update the threshold and open duration; current state is kept.
+    pub fn configure(&self, failure_threshold: u32, open_for: Duration) {
+        let mut inner = self.inner.lock().expect("breaker lock");
+        inner.failure_threshold = failure_threshold.max(1);
+        inner.open_for = open_for;
+    }
+
+    /// Fail fast with `UpstreamUnavailable` while open. Once `open_for` has elapsed,
+    /// admit one half-open probe at a time.
+    pub fn acquire(&self) -> Result<BreakerPermit<'_>, GatewayError> {
+        let mut inner = self.inner.lock().expect("breaker lock");
+        let probe = match inner.state {
+            BreakerState::Closed => false,
+            BreakerState::Open => {
+                let open_for = inner.open_for;
+                let elapsed = inner.opened_at.map_or(open_for, |at| at.elapsed());
+                if elapsed < open_for {
+                    return Err(self.unavailable(open_for - elapsed));
+                }
+                inner.state = BreakerState::HalfOpen;
+                tracing::info!(upstream = %self.upstream, "circuit half-open, probing");
+                true
+            }
+            BreakerState::HalfOpen if !inner.probing => true,
+            BreakerState::HalfOpen => return Err(self.unavailable(Duration::ZERO)),
+        };
+        inner.probing |= probe;
+        Ok(BreakerPermit {
+            breaker: self,
+            probe,
+            settled: false,
+        })
+    }
+
+    fn record_success(&self) {
+        let mut inner = self.inner.lock().expect("breaker lock");
+        if inner.state != BreakerState::Closed {
+            tracing::info!(upstream = %self.upstream, "circuit closed");
+        }
+        inner.state = BreakerState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.probing = false;
+    }
+
+    fn record_failure(&self) {
+        let mut inner = self.inner.lock().expect("breaker lock");
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        inner.probing = false;
+        let trip = inner.state == BreakerState::HalfOpen
+            || inner.consecutive_failures >= inner.failure_threshold;
+        if trip && inner.state != BreakerState::Open {
+            tracing::warn!(
+                upstream = %self.upstream,
+                failures = inner.consecutive_failures,
+                "circuit opened"
+            );
+        }
+        if trip {
+            inner.state = BreakerState::Open;
+            inner.opened_at = Some(Instant::now());
+        }
+    }
+
+    /// An abandoned probe frees the half-open slot without counting as an outcome.
+    fn release_probe(&self) {
+        let mut inner = self.inner.lock().expect("breaker lock");
+        if inner.state == BreakerState::HalfOpen {
+            inner.probing = false;
+        }
+    }
+
+    pub fn snapshot(&self) -> BreakerSnapshot {
+        let inner = self.inner.lock().expect("breaker lock");
+        BreakerSnapshot {
+            state: inner.state,
+            consecutive_failures: inner.consecutive_failures,
+        }
+    }
+
+    fn unavailable(&self, retry_after: Duration) -> GatewayError {
+        GatewayError::UpstreamUnavailable {
+            upstream: self.upstream.clone(),
+            reason: "circuit open".to_string(),
+            retry_after_secs: Some(retry_after.as_secs_f64().ceil() as u64),
+        }
+    }
+}
+
+/// Admission granted by `CircuitBreaker::acquire`. Report the outcome with
+/// `success` or `failure`; dropping the permit unreported (e.g. because the
+/// caller's future was cancelled) releases a half-open probe so the breaker
+/// cannot get stuck waiting for it.
+#[must_use = "report the outcome with `success` or `failure`"]
+pub struct BreakerPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+    settled: bool,
+}
+
+impl BreakerPermit<'_> {
+    pub fn success(mut self) {
+        self.settled = true;
+        self.breaker.record_success();
+    }
+
+    pub fn failure(mut self) {
+        self.settled = true;
+        self.breaker.record_failure();
+    }
+
+    /// Run `op` under this permit and report its outcome. Outage failures
+    /// count towards opening the breaker; any other outcome shows the upstream
+    /// is reachable.
+    pub async fn run<T, Fut>(self, op: Fut) -> Result<T, GatewayError>
+    where
+        Fut: std::future::Future<Output = Result<T, AttemptError>>,
+    {
+        match op.await {
+            Ok(v) => {
+                self.success();
+                Ok(v)
+            }
+            Err(e) => {
+                let upstream = self.breaker.upstream.clone();
+                if e.is_unavailable() {
+                    self.failure();
+                } else {
+                    self.success();
+                }
+                Err(e.into_gateway(&upstream))
+            }
+        }
+    }
+}
+
+impl Drop for BreakerPermit<'_> {
+    fn drop(&mut self) {
+        if !self.settled && self.probe {
+            self.breaker.release_probe();
+        }
+    }
+}
+
+fn registry() -> &'static Mutex<HashMap<String, Arc<CircuitBreaker>>> {
+    static BREAKERS: OnceLock<Mutex<HashMap<String, Arc<CircuitBreaker>>>> = OnceLock::new();
+    BREAKERS.get_or_init(Default::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_opens_after_threshold_and_fails_fast() {
+        let b = CircuitBreaker::new("http://up", 2, Duration::from_secs(30));
+        assert!(b.acquire().is_ok());
+        b.record_failure();
+        assert_eq!(b.snapshot().state, BreakerState::Closed);
+        b.record_failure();
+        assert_eq!(b.snapshot().state, BreakerState::Open);
+
+        match b.acquire().err() {
+            Some(GatewayError::UpstreamUnavailable {
+                upstream,
+                retry_after_secs: Some(secs),
+                ..
+            }) => {
+                assert_eq!(upstream, "http://up");
+                assert!(secs > 0 && secs <= 30);
+            }
+            other => panic!("expected UpstreamUnavailable, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn it_admits_one_half_open_probe() {
+        let b = CircuitBreaker::new("http://up", 1, Duration::ZERO);
+        b.record_failure();
+        assert_eq!(b.snapshot().state, BreakerState::Open);
+
+        let probe = b.acquire().expect("probe admitted");
+        assert_eq!(b.snapshot().state, BreakerState::HalfOpen);
+        assert!(b.acquire().is_err());
+
+        // A failed probe re-opens; a successful one closes.
+        probe.failure();
+        assert_eq!(b.snapshot().state, BreakerState::Open);
+        b.acquire().expect("probe admitted").success();
+        assert_eq!(b.snapshot().state, BreakerState::Closed);
+        assert_eq!(b.snapshot().consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn run_only_counts_outages() {
+        let b = CircuitBreaker::new("http://up", 1, Duration::from_secs(30));
+        let err = b
+            .acquire()
+            .unwrap()
+            .run(async { Err::<(), _>(AttemptError::BadResponse("upstream status 400".into())) })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GatewayError::UpstreamBadResponse { .. }));
+        assert_eq!(err.to_string(), "upstream status 400");
+        assert_eq!(b.snapshot().state, BreakerState::Closed);
+
+        let _ = b
+            .acquire()
+            .unwrap()
+            .run(async { Err::<(), _>(AttemptError::Unavailable("connect refused".into())) })
+            .await;
+        assert_eq!(b.snapshot().state, BreakerState::Open);
+        assert!(matches!(
+            b.acquire().err(),
+            Some(GatewayError::UpstreamUnavailable { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn a_cancelled_probe_does_not_wedge_the_breaker() {
+        let b = CircuitBreaker::new("http://up", 1, Duration::ZERO);
+        b.record_failure();
+
+        // The probe is admitted, then dropped mid-flight when the timeout fires.
+        let probe = b
+            .acquire()
+            .expect("probe admitted")
+            .run(std::future::pending::<Result<(), AttemptError>>());
+        assert!(tokio::time::timeout(Duration::from_millis(10), probe)
+            .await
+            .is_err());
+        assert_eq!(b.snapshot().state, BreakerState::HalfOpen);
+
+        let out = b
+            .acquire()
+            .expect("probe admitted again")
+            .run(async { Ok::<_, AttemptError>(1) })
+            .await;
+        assert_eq!(out.unwrap(), 1);
+        assert_eq!(b.snapshot().state, BreakerState::Closed);
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let b = CircuitBreaker::new("http://up", 2, Duration::from_secs(30));
+        b.record_failure();
+        b.record_success();
+        b.record_failure();
+        assert_eq!(b.snapshot().state, BreakerState::Closed);
+    }
+
+    #[test]
+    fn shared_breakers_are_per_upstream() {
+        let a = CircuitBreaker::for_upstream("http://breaker-test-a");
+        let again = CircuitBreaker::for_upstream("http://breaker-test-a");
+        assert!(Arc::ptr_eq(&a, &again));
+        let b = CircuitBreaker::for_upstream("http://breaker-test-b");
+        assert!(!Arc::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    fn configure_changes_threshold_without_resetting_state() {
+        let b = CircuitBreaker::new("http://up", 5, Duration::from_secs(30));
+        b.record_failure();
+        b.configure(2, Duration::from_secs(30));
+        assert_eq!(b.snapshot().consecutive_failures, 1);
+        b.record_failure();
+        assert_eq!(b.snapshot().state, BreakerState::Open);
+    }
+}
@@ -0,0 +1,406 @@
+//! Content-addressed result cache for upstream calls.
+//!
+//! Entries are keyed on a namespace (tool or upstream) plus a SHA-256 of the
+//! exact input, so the persisted file holds no request text in its keys. Text
+//! is never normalised (NFC, trimming) for the key because results carry
+//! offsets into it. Evicts least-recently-used entries beyond `capacity` and expires
+//! entries after `ttl`. With a `path`, entries are loaded at startup and
+//! written back at most every [`FLUSH_INTERVAL`] and on graceful shutdown.
+//! Writes run on the blocking pool, outside the cache lock.
+
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::infra::config::Config;
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+pub struct ResultCache {
+    capacity: usize,
+    ttl: Duration,
+    path: Option<PathBuf>,
+    state: Arc<Mutex<State>>,
+    /// Serialises writes to `path`; holds the generation last written.
+    written: Arc<Mutex<u64>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+struct State {
+    entries: LruCache<String, Arc<Entry>>,
+    generation: u64,
+    dirty: bool,
+    last_flush: Option<Instant>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    value: serde_json::Value,
+    /// Unix epoch millis after which the entry is stale.
+    expires_at: u64,
+}
+
+#[derive(Deserialize)]
+struct Snapshot {
+    entries: HashMap<String, Entry>,
+}
+
+#[derive(Serialize)]
+struct SnapshotRef<'a> {
+    entries: HashMap<&'a str, &'a Entry>,
+}
+
+/// Entries captured under the lock, written to disk after it is released.
+struct PendingWrite {
+    path: PathBuf,
+    generation: u64,
+    entries: Vec<(String, Arc<Entry>)>,
+    state: Arc<Mutex<State>>,
+    written: Arc<Mutex<u64>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub capacity: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl PendingWrite {
+    fn write(self) {
+        let mut written = self.written.lock().expect("cache write lock");
+        // A newer snapshot already reached the disk
+        if *written >= self.generation {
+            return;
+        }
+        let snapshot = SnapshotRef {
+            entries: self
+                .entries
+                .iter()
+                .map(|(k, e)| (k.as_str(), &**e))
+                .collect(),
+        };
+        let tmp = self.path.with_extension("tmp");
+        let result = serde_json::to_vec(&snapshot)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| std::fs::write(&tmp, bytes).map_err(|e| e.to_string()))
+            .and_then(|_| std::fs::rename(&tmp, &self.path).map_err(|e| e.to_string()));
+        match result {
+            Ok(()) => *written = self.generation,
+            Err(e) => {
+                tracing::warn!(path = %self.path.display(), error = %e, "failed to persist result cache");
+                self.state.lock().expect("cache lock").dirty = true;
+            }
+        }
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+impl ResultCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        let slots = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            capacity,
+            ttl,
+            path: None,
+            state: Arc::new(Mutex::new(State {
+                entries: LruCache::new(slots),
+                generation: 0,
+                dirty: false,
+                last_flush: None,
+            })),
+            written: Arc::new(Mutex::new(0)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Persist entries to `path`, loading any unexpired entries already there.
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        match std::fs::read(&path) {
+            Ok(bytes) => match serde_json::from_slice::<Snapshot>(&bytes) {
+                Ok(snapshot) => {
+                    let now = now_millis();
+                    let mut state = self.state.lock().expect("cache lock");
+                    for (key, entry) in snapshot
+                        .entries
+                        .into_iter()
+                        .filter(|(_, e)| e.expires_at > now)
+                        .take(self.capacity)
+                    {
+                        state.entries.put(key, Arc::new(entry));
+                    }
+                    tracing::info!(path = %path.display(), entries = state.entries.len(), "loaded result cache");
+                }
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "ignoring unreadable result cache")
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "failed to read result cache")
+            }
+        }
+        self.path = Some(path);
+        self
+    }
+
+    /// Cache configured by `cfg`, or `None` when caching is disabled (capacity 0).
+    pub fn from_config(cfg: &Config) -> Option<Arc<ResultCache>> {
+        if cfg.cache_capacity == 0 {
+            return None;
+        }
+        let cache = ResultCache::new(cfg.cache_capacity, cfg.cache_ttl);
+        let cache = match &cfg.cache_path {
+            Some(path) => cache.with_path(path),
+            None => cache,
+        };
+        Some(Arc::new(cache))
+    }
+
+    /// Build a key from a namespace and the parts that determine the result.
+    /// The parts are hashed, not stored. They are not normalised either: the
+    /// cached offsets only hold for the exact bytes that were checked, so
+    /// `"cota"` and `" cota"` must not share an entry.
+    pub fn key(namespace: &str, parts: &[&str]) -> String {
+        let mut hasher = Sha256::new();
+        for part in parts {
+            hasher.update(part.as_bytes());
+            hasher.update([0x1f]);
+        }
+        let digest: String = hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        format!("{namespace}\u{1f}{digest}")
+    }
+
+    pub fn get(&self, key: &str) -> Option<serde_json::Value> {
+        let mut state = self.state.lock().expect("cache lock");
+        let now = now_millis();
+        let cached = state
+            .entries
+            .get(key)
+            .map(|entry| (entry.expires_at > now).then(|| entry.value.clone()));
+        let found = match cached {
+            Some(Some(value)) => Some(value),
+            Some(None) => {
+                state.entries.pop(key);
+                state.dirty = true;
+                None
+            }
+            None => None,
+        };
+        drop(state);
+        let counter = if found.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        found
+    }
+
+    pub fn insert(&self, key: String, value: serde_json::Value) {
+        if self.capacity == 0 {
+            return;
+        }
+        let entry = Arc::new(Entry {
+            value,
+            expires_at: now_millis().saturating_add(self.ttl.as_millis() as u64),
+        });
+        let pending = {
+            let mut state = self.state.lock().expect("cache lock");
+            state.entries.put(key, entry);
+            state.dirty = true;
+            let due = state
+                .last_flush
+                .is_none_or(|at| at.elapsed() >= FLUSH_INTERVAL);
+            if due {
+                self.take_snapshot(&mut state)
+            } else {
+                None
+            }
+        };
+        if let Some(pending) = pending {
+            match tokio::runtime::Handle::try_current() {
+                Ok(rt) => drop(rt.spawn_blocking(move || pending.write())),
+                Err(_) => pending.write(),
+            }
+        }
+    }
+
+    /// Write entries to disk now, if persistence is enabled and anything
+    /// changed. Blocks on file IO, so async callers should use `spawn_blocking`.
+    pub fn flush(&self) {
+        let pending = {
+            let mut state = self.state.lock().expect("cache lock");
+            self.take_snapshot(&mut state)
+        };
+        if let Some(pending) = pending {
+            pending.write();
+        }
+    }
+
+    /// Capture the entries to persist. Only `Arc`s are cloned under the lock;
+    /// serialising and writing happen after it is released.
+    fn take_snapshot(&self, state: &mut State) -> Option<PendingWrite> {
+        let path = self.path.clone()?;
+        if !state.dirty {
+            return None;
+        }
+        state.dirty = false;
+        state.last_flush = Some(Instant::now());
+        state.generation += 1;
+        Some(PendingWrite {
+            path,
+            generation: state.generation,
+            entries: state
+                .entries
+                .iter()
+                .map(|(k, e)| (k.clone(), e.clone()))
+                .collect(),
+            state: self.state.clone(),
+            written: self.written.clone(),
+        })
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let entries = self.state.lock().expect("cache lock").entries.len();
+        CacheStats {
+            entries,
+            capacity: self.capacity,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn it_counts_hits_and_misses() {
+        let cache = ResultCache::new(4, Duration::from_secs(60));
+        let key = ResultCache::key("ns", &["Dia duit"]);
+        assert!(cache.get(&key).is_none());
+        cache.insert(key.clone(), json!([1]));
+        assert_eq!(cache.get(&key), Some(json!([1])));
+        assert!(cache
+            .get(&ResultCache::key("other", &["Dia duit"]))
+            .is_none());
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 1));
+    }
+
+    #[test]
+    fn keys_hash_the_text_but_keep_it_exact() {
+        let key = ResultCache::key("ns", &["Dia duit"]);
+        assert!(key.starts_with("ns\u{1f}"));
+        assert!(!key.contains("Dia duit"));
+        assert_ne!(key, ResultCache::key("ns", &[" Dia duit"]));
+        // Part boundaries still count.
+        assert_ne!(
+            ResultCache::key("ns", &["ab", "c"]),
+            ResultCache::key("ns", &["a", "bc"])
+        );
+    }
+
+    #[test]
+    fn it_evicts_least_recently_used() {
+        let cache = ResultCache::new(2, Duration::from_secs(60));
+        cache.insert("a".into(), json!(1));
+        cache.insert("b".into(), json!(2));
+        assert!(cache.get("a").is_some());
+        cache.insert("c".into(), json!(3));
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn it_expires_entries_after_ttl() {
+        let cache = ResultCache::new(2, Duration::ZERO);
+        cache.insert("a".into(), json!(1));
+        assert!(cache.get("a").is_none());
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn it_persists_to_disk_and_reloads() {
+        let path = std::env::temp_dir().join(format!("result-cache-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let cache = ResultCache::new(4, Duration::from_secs(60)).with_path(&path);
+        cache.insert("a".into(), json!({"issues": []}));
+        cache.insert("b".into(), json!(2));
+        cache.flush();
+
+        let reloaded = ResultCache::new(4, Duration::from_secs(60)).with_path(&path);
+        assert_eq!(reloaded.get("a"), Some(json!({"issues": []})));
+        assert_eq!(reloaded.get("b"), Some(json!(2)));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn from_config_follows_cache_settings() {
+        let cfg = Config {
+            cache_capacity: 7,
+            ..Config::default()
+        };
+        let cache = ResultCache::from_config(&cfg).expect("cache enabled");
+        assert_eq!(cache.stats().capacity, 7);
+
+        let off = Config {
+            cache_capacity: 0,
+            ..Config::default()
+        };
+        assert!(ResultCache::from_config(&off).is_none());
+    }
+
+    #[tokio::test]
+    async fn it_persists_in_the_background() {
+        let path =
+            std::env::temp_dir().join(format!("result-cache-bg-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let cache = ResultCache::new(4, Duration::from_secs(60)).with_path(&path);
+        cache.insert("a".into(), json!(1));
+        // The write is in flight on the blocking pool; lookups are not held up
+        assert_eq!(cache.get("a"), Some(json!(1)));
+        for _ in 0..100 {
+            if path.exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let reloaded = ResultCache::new(4, Duration::from_secs(60)).with_path(&path);
+        assert_eq!(reloaded.get("a"), Some(json!(1)));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn zero_capacity_stores_nothing() {
+        let cache = ResultCache::new(0, Duration::from_secs(60));
+        cache.insert("a".into(), json!(1));
+        assert!(cache.get("a").is_none());
+    }
+}
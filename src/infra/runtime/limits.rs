@@ -1,32 +1,120 @@
 use std::time::Duration;
 
+use crate::core::error::GatewayError;
+use crate::infra::config::UpstreamConfig;
+
+/// Failure of a single upstream HTTP attempt.
+#[derive(Debug)]
+pub enum AttemptError {
+    /// Connect errors and 5xx responses.
+    Unavailable(String),
+    /// The request did not complete within the client timeout.
+    Timeout(String),
+    /// 4xx responses or an undecodable body.
+    BadResponse(String),
+}
+
+impl AttemptError {
+    /// Outages (connect errors, timeouts, 5xx) are retried and count against the
+    /// upstream's circuit breaker; bad responses are neither.
+    pub fn is_unavailable(&self) -> bool {
+        !matches!(self, AttemptError::BadResponse(_))
+    }
+
+    pub fn into_gateway(self, upstream: &str) -> GatewayError {
+        let upstream = upstream.to_string();
+        match self {
+            AttemptError::Unavailable(reason) => GatewayError::UpstreamUnavailable {
+                upstream,
+                reason,
+                retry_after_secs: None,
+            },
+            AttemptError::Timeout(reason) => GatewayError::Timeout { upstream, reason },
+            AttemptError::BadResponse(reason) => {
+                GatewayError::UpstreamBadResponse { upstream, reason }
+            }
+        }
+    }
+}
+
+impl From<reqwest::Error> for AttemptError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            AttemptError::Timeout(e.to_string())
+        } else if e.is_decode() {
+            AttemptError::BadResponse(e.to_string())
+        } else {
+            AttemptError::Unavailable(e.to_string())
+        }
+    }
+}
+
 /// Build a reqwest client with sane defaults (timeouts, redirects disabled by default).
 pub fn make_http_client() -> reqwest::Client {
-    reqwest::Client::builder()
-        .connect_timeout(Duration::from_secs(2))
-        .timeout(Duration::from_secs(6))
-        .build()
-        .expect("reqwest client")
+    make_upstream_client(&UpstreamConfig::default())
+}
+
+/// Build a reqwest client from per-upstream timeout, pool and HTTP/2 settings.
+pub fn make_upstream_client(cfg: &UpstreamConfig) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(cfg.connect_timeout)
+        .timeout(cfg.request_timeout)
+        .pool_max_idle_per_host(cfg.pool_max_idle);
+    if cfg.http2 {
+        builder = builder.http2_prior_knowledge();
+    }
+    builder.build().expect("reqwest client")
+}
+
+/// How many times to retry and how long to back off between attempts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub retries: u32,
+    pub backoff_cap: Duration,
+    /// Sleep a random 50–100% of each backoff step to spread out retries.
+    pub jitter: bool,
 }
 
 /// Simple exponential backoff utility for async ops.
-pub async fn retry_async<T, E, Fut, F>(mut attempts: u32, mut op: F) -> Result<T, E>
+#[allow(dead_code)]
+pub async fn retry_async<T, E, Fut, F>(attempts: u32, op: F) -> Result<T, E>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+{
+    let policy = RetryPolicy {
+        retries: attempts,
+        backoff_cap: Duration::from_secs(1),
+        jitter: false,
+    };
+    retry_if(policy, |_| true, op).await
+}
+
+/// Exponential backoff that only retries errors for which `retryable` is true.
+pub async fn retry_if<T, E, Fut, F, P>(policy: RetryPolicy, retryable: P, mut op: F) -> Result<T, E>
 where
     F: FnMut(u32) -> Fut,
     Fut: std::future::Future<Output = Result<T, E>>,
+    P: Fn(&E) -> bool,
 {
+    let mut attempts = policy.retries;
     let mut try_num: u32 = 0;
-    let mut delay_ms: u64 = 50;
+    let mut delay = Duration::from_millis(50).min(policy.backoff_cap);
     loop {
         match op(try_num).await {
             Ok(v) => return Ok(v),
             Err(e) => {
-                if attempts == 0 {
+                if attempts == 0 || !retryable(&e) {
                     return Err(e);
                 }
                 attempts -= 1;
-                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
-                delay_ms = (delay_ms * 2).min(1_000);
+                let sleep = if policy.jitter {
+                    delay.mul_f64(0.5 + fastrand::f64() / 2.0)
+                } else {
+                    delay
+                };
+                tokio::time::sleep(sleep).await;
+                delay = (delay * 2).min(policy.backoff_cap);
                 try_num += 1;
             }
         }
@@ -53,4 +141,34 @@ mod tests {
         .await;
         assert_eq!(res.unwrap(), 42);
     }
+
+    #[tokio::test]
+    async fn retry_if_stops_on_non_retryable_errors() {
+        use super::{retry_if, RetryPolicy};
+        use std::time::Duration;
+        let policy = RetryPolicy {
+            retries: 5,
+            backoff_cap: Duration::from_millis(1),
+            jitter: true,
+        };
+        let mut calls = 0;
+        let res: Result<(), &str> = retry_if(
+            policy,
+            |e| *e == "retry",
+            |_| {
+                calls += 1;
+                let c = calls;
+                async move {
+                    if c < 3 {
+                        Err("retry")
+                    } else {
+                        Err("fatal")
+                    }
+                }
+            },
+        )
+        .await;
+        assert_eq!(res.unwrap_err(), "fatal");
+        assert_eq!(calls, 3);
+    }
 }
@@ -12,6 +12,7 @@ use rmcp::transport::streamable_http_server::tower::{
 pub use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
 pub use rmcp::ServerHandler;
 
+#[allow(dead_code)]
 pub async fn serve_stdio<H>(
     factory: impl FnOnce() -> (H, ToolRouter<H>),
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
@@ -46,6 +47,7 @@ where
     Ok(())
 }
 
+#[allow(dead_code)]
 pub fn make_streamable_http_service<H>(
     factory: impl Fn() -> (H, ToolRouter<H>) + Send + Sync + Clone + 'static,
     session_mgr: Arc<LocalSessionManager>,
@@ -62,24 +64,66 @@ where
     StreamableHttpService::new(service_factory, session_mgr, cfg)
 }
 
+/// Serve a handler that answers tool requests itself (no static `ToolRouter`) over stdio.
+pub async fn serve_stdio_handler<H>(
+    handler: H,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+    H: ServerHandler,
+{
+    serve_handler_with_io(handler, tokio::io::stdin(), tokio::io::stdout()).await
+}
+
+/// Testable variant of [`serve_stdio_handler`] that accepts arbitrary IO.
+pub async fn serve_handler_with_io<H, R, W>(
+    handler: H,
+    reader: R,
+    writer: W,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+    H: ServerHandler,
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+    W: tokio::io::AsyncWrite + Unpin + Send + 'static,
+{
+    let running = serve_server(handler, (reader, writer)).await?;
+    running.waiting().await?;
+    Ok(())
+}
+
+/// Streamable HTTP service for a handler that answers tool requests itself,
+/// one handler per session.
+pub fn make_streamable_http_handler_service<H>(
+    factory: impl Fn() -> H + Send + Sync + 'static,
+    session_mgr: Arc<LocalSessionManager>,
+) -> StreamableHttpService<H, LocalSessionManager>
+where
+    H: ServerHandler,
+{
+    let cfg = StreamableHttpServerConfig::default();
+    StreamableHttpService::new(move || Ok(factory()), session_mgr, cfg)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::clients::gramadoir::GramadoirRemote;
-    use crate::tools::grammar::tool_router::GrammarSvc;
     use std::sync::Arc;
     use tokio::io::{duplex, AsyncReadExt, AsyncWriteExt};
     use tokio::time::{timeout, Duration, Instant};
 
+    /// A handler without tools, enough to exercise the transports.
+    #[derive(Clone)]
+    struct NoTools;
+
+    impl ServerHandler for NoTools {}
+
+    fn no_tools() -> (NoTools, ToolRouter<NoTools>) {
+        (NoTools, ToolRouter::new())
+    }
+
     #[tokio::test]
     async fn test_make_streamable_http_service() {
         let session_mgr = Arc::new(LocalSessionManager::default());
-        let factory = || {
-            let checker = GramadoirRemote::new("http://test".to_string());
-            let handler = GrammarSvc { checker };
-            let tools = GrammarSvc::router();
-            (handler, tools)
-        };
+        let factory = no_tools;
 
         let _service = make_streamable_http_service(factory, session_mgr);
     }
@@ -87,12 +131,7 @@ mod tests {
     #[tokio::test]
     async fn test_make_streamable_http_service_with_different_factory() {
         let session_mgr = Arc::new(LocalSessionManager::default());
-        let factory = || {
-            let checker = GramadoirRemote::new("http://different-test".to_string());
-            let handler = GrammarSvc { checker };
-            let tools = GrammarSvc::router();
-            (handler, tools)
-        };
+        let factory = no_tools;
 
         let _service = make_streamable_http_service(factory, session_mgr);
     }
@@ -100,24 +139,14 @@ mod tests {
     #[tokio::test]
     async fn make_streamable_http_service_uses_session_manager() {
         let session_mgr = Arc::new(LocalSessionManager::default());
-        let factory = || {
-            let checker = GramadoirRemote::new("http://test".to_string());
-            let handler = GrammarSvc { checker };
-            let tools = GrammarSvc::router();
-            (handler, tools)
-        };
+        let factory = no_tools;
         let _service = make_streamable_http_service(factory, session_mgr.clone());
         // If session manager type mismatched, this would not compile; runtime test is smoke only.
     }
 
     #[test]
     fn test_serve_stdio_factory_called() {
-        let factory = || {
-            let checker = GramadoirRemote::new("http://test".to_string());
-            let handler = GrammarSvc { checker };
-            let tools = GrammarSvc::router();
-            (handler, tools)
-        };
+        let factory = no_tools;
 
         // Test that factory can be called (we can't easily test the full stdio flow)
         let _ = factory();
@@ -126,12 +155,7 @@ mod tests {
     #[tokio::test]
     async fn test_serve_stdio_propagates_error() {
         // Create a handler whose service will error immediately by using an invalid IO pair
-        let factory = || {
-            let checker = GramadoirRemote::new("".to_string());
-            let handler = GrammarSvc { checker };
-            let tools = GrammarSvc::router();
-            (handler, tools)
-        };
+        let factory = no_tools;
         // We can't easily force serve_server to error without IO, so just assert the function type compiles
         let _ = factory();
     }
@@ -141,12 +165,7 @@ mod tests {
         let (mut client, server) = duplex(1024);
         let (srv_r, srv_w) = tokio::io::split(server);
 
-        let factory = || {
-            let checker = GramadoirRemote::new("http://test".to_string());
-            let handler = GrammarSvc { checker };
-            let tools = GrammarSvc::router();
-            (handler, tools)
-        };
+        let factory = no_tools;
 
         let serve = tokio::spawn(async move { serve_stdio_with_io(factory, srv_r, srv_w).await });
 
@@ -161,12 +180,7 @@ mod tests {
         let (mut client, server) = duplex(1024);
         let (srv_r, srv_w) = tokio::io::split(server);
 
-        let factory = || {
-            let checker = GramadoirRemote::new("http://test".to_string());
-            let handler = GrammarSvc { checker };
-            let tools = GrammarSvc::router();
-            (handler, tools)
-        };
+        let factory = no_tools;
 
         let serve = tokio::spawn(async move { serve_stdio_with_io(factory, srv_r, srv_w).await });
 
@@ -183,12 +197,7 @@ mod tests {
         let (mut client, server) = duplex(4096);
         let (srv_r, srv_w) = tokio::io::split(server);
 
-        let factory = || {
-            let checker = GramadoirRemote::new("http://test".to_string());
-            let handler = GrammarSvc { checker };
-            let tools = GrammarSvc::router();
-            (handler, tools)
-        };
+        let factory = no_tools;
 
         let serve = tokio::spawn(async move { serve_stdio_with_io(factory, srv_r, srv_w).await });
 
@@ -229,4 +238,46 @@ mod tests {
 
     #[tokio::test]
     async fn test_serve_stdio_with_io_two_lists() {}
+
+    #[tokio::test]
+    async fn serve_handler_with_io_lists_live_tools_and_advertises_changes() {
+        use crate::infra::config::Config;
+        use crate::tools::registry::live::LiveRegistry;
+        use crate::tools::registry::tool_router::RegistrySvc;
+
+        let (mut client, server) = duplex(16 * 1024);
+        let (srv_r, srv_w) = tokio::io::split(server);
+        let handler = RegistrySvc::new(LiveRegistry::new(Config::default()));
+        let serve = tokio::spawn(serve_handler_with_io(handler, srv_r, srv_w));
+
+        let init = b"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"initialize\",\"params\":{\"protocolVersion\":\"2025-03-26\",\"capabilities\":{},\"clientInfo\":{\"name\":\"test\",\"version\":\"0.0.0\"}}}\n";
+        let inited =
+            b"{\"jsonrpc\":\"2.0\",\"method\":\"notifications/initialized\",\"params\":{}}\n";
+        let list = b"{\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"tools/list\",\"params\":{}}\n";
+        client.write_all(init).await.unwrap();
+        client.write_all(inited).await.unwrap();
+        client.write_all(list).await.unwrap();
+
+        let mut buf = [0u8; 4096];
+        let mut total = Vec::new();
+        let deadline = Instant::now() + Duration::from_millis(2000);
+        while Instant::now() < deadline {
+            match timeout(Duration::from_millis(100), client.read(&mut buf)).await {
+                Ok(Ok(n)) if n > 0 => {
+                    total.extend_from_slice(&buf[..n]);
+                    if String::from_utf8_lossy(&total).contains("hello.echo") {
+                        break;
+                    }
+                }
+                Ok(Err(_)) => break,
+                _ => continue,
+            }
+        }
+        let out = String::from_utf8_lossy(&total);
+        assert!(out.contains("\"listChanged\":true"));
+        assert!(out.contains("hello.echo"));
+
+        client.shutdown().await.unwrap();
+        let _ = serve.await.unwrap();
+    }
 }
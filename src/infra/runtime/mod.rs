@@ -1,5 +1,10 @@
+pub mod breaker;
+pub mod cache;
 pub mod limits;
 pub mod mcp_transport;
+pub mod queue;
+pub mod rate_limit;
+pub mod reload;
 pub mod session;
 
 #[cfg(test)]
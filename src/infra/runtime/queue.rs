@@ -0,0 +1,306 @@
+//! Per-upstream concurrency limits with a bounded wait queue.
+//!
+//! At most `max_concurrent` requests are in flight to an upstream. Further
+//! requests wait in line, up to `max_queue` of them and for no longer than
+//! `queue_timeout`; past either bound they fail straight away with an
+//! overload error instead of piling onto an upstream that cannot keep up.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::core::error::GatewayError;
+use crate::infra::config::UpstreamConfig;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueSnapshot {
+    pub in_flight: usize,
+    pub queued: usize,
+    pub max_concurrent: usize,
+    pub max_queue: usize,
+}
+
+pub struct UpstreamQueue {
+    upstream: String,
+    settings: Mutex<Settings>,
+    slots: Arc<Semaphore>,
+    /// Slots still to retire after `max_concurrent` was lowered while they
+    /// were in use.
+    excess: Arc<AtomicUsize>,
+    in_flight: Arc<AtomicUsize>,
+    queued: AtomicUsize,
+}
+
+struct Settings {
+    max_concurrent: usize,
+    max_queue: usize,
+    queue_timeout: Duration,
+}
+
+/// A slot on the upstream, released on drop (or retired, if the limit has
+/// been lowered since it was taken).
+pub struct QueuePermit {
+    slot: Option<OwnedSemaphorePermit>,
+    excess: Arc<AtomicUsize>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for QueuePermit {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+        if take_up_to(&self.excess, 1) == 1 {
+            if let Some(slot) = self.slot.take() {
+                slot.forget();
+            }
+        }
+    }
+}
+
+/// Subtract up to `n` from `counter`, returning how much was taken.
+fn take_up_to(counter: &AtomicUsize, n: usize) -> usize {
+    counter
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |v| Some(v - v.min(n)))
+        .map_or(0, |v| v.min(n))
+}
+
+/// Takes a request back out of the queue however its wait ends.
+struct Waiting<'a>(&'a AtomicUsize);
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl UpstreamQueue {
+    pub fn new(
+        upstream: impl Into<String>,
+        max_concurrent: usize,
+        max_queue: usize,
+        queue_timeout: Duration,
+    ) -> Self {
+        let max_concurrent = max_concurrent.max(1);
+        Self {
+            upstream: upstream.into(),
+            settings: Mutex::new(Settings {
+                max_concurrent,
+                max_queue,
+                queue_timeout,
+            }),
+            slots: Arc::new(Semaphore::new(max_concurrent)),
+            excess: Arc::new(AtomicUsize::new(0)),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    /// Process-wide queue for `upstream` (its base URL). New queues start with
+    /// the `UpstreamConfig` defaults; clients apply their settings via `configure`.
+    pub fn for_upstream(upstream: &str) -> Arc<UpstreamQueue> {
+        let mut queues = registry().lock().expect("queue registry lock");
+        queues
+            .entry(upstream.to_string())
+            .or_insert_with(|| {
+                let defaults = UpstreamConfig::default();
+                Arc::new(UpstreamQueue::new(
+                    upstream,
+                    defaults.max_concurrent,
+                    defaults.max_queue,
+                    defaults.queue_timeout,
+                ))
+            })
+            .clone()
+    }
+
+    /// Update the limits. Requests already in flight finish undisturbed, but
+    /// count against the new concurrency limit: after lowering it, nothing new
+    /// is admitted until in-flight requests have dropped below it.
+    pub fn configure(&self, max_concurrent: usize, max_queue: usize, queue_timeout: Duration) {
+        let max_concurrent = max_concurrent.max(1);
+        let mut settings = self.settings.lock().expect("queue lock");
+        if max_concurrent > settings.max_concurrent {
+            // Cancel retirements still pending before adding new slots
+            let grow = max_concurrent - settings.max_concurrent;
+            let cancelled = take_up_to(&self.excess, grow);
+            self.slots.add_permits(grow - cancelled);
+        } else if max_concurrent < settings.max_concurrent {
+            // Retire free slots now and busy ones as their requests finish
+            let shrink = settings.max_concurrent - max_concurrent;
+            let forgotten = self.slots.forget_permits(shrink);
+            self.excess.fetch_add(shrink - forgotten, Ordering::AcqRel);
+        }
+        settings.max_concurrent = max_concurrent;
+        settings.max_queue = max_queue;
+        settings.queue_timeout = queue_timeout;
+    }
+
+    /// Wait for a free slot, or fail with `UpstreamUnavailable` when the queue
+    /// is full or the wait outlasts `queue_timeout`.
+    pub async fn acquire(&self) -> Result<QueuePermit, GatewayError> {
+        let slots = self.slots.clone();
+        let (max_queue, queue_timeout) = {
+            let settings = self.settings.lock().expect("queue lock");
+            (settings.max_queue, settings.queue_timeout)
+        };
+
+        let slot = match slots.clone().try_acquire_owned() {
+            Ok(slot) => slot,
+            Err(_) => {
+                let _waiting = Waiting(&self.queued);
+                if self.queued.fetch_add(1, Ordering::Relaxed) >= max_queue {
+                    tracing::warn!(upstream = %self.upstream, max_queue, "upstream queue full");
+                    return Err(self.overloaded(format!("queue full ({max_queue} waiting)")));
+                }
+                match tokio::time::timeout(queue_timeout, slots.acquire_owned()).await {
+                    Ok(Ok(slot)) => slot,
+                    Ok(Err(_)) => return Err(self.overloaded("queue closed".into())),
+                    Err(_) => {
+                        tracing::warn!(upstream = %self.upstream, "timed out in upstream queue");
+                        return Err(self.overloaded(format!(
+                            "no free slot within {}ms",
+                            queue_timeout.as_millis()
+                        )));
+                    }
+                }
+            }
+        };
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        Ok(QueuePermit {
+            slot: Some(slot),
+            excess: self.excess.clone(),
+            in_flight: self.in_flight.clone(),
+        })
+    }
+
+    pub fn snapshot(&self) -> QueueSnapshot {
+        let settings = self.settings.lock().expect("queue lock");
+        QueueSnapshot {
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+            queued: self.queued.load(Ordering::Relaxed),
+            max_concurrent: settings.max_concurrent,
+            max_queue: settings.max_queue,
+        }
+    }
+
+    fn overloaded(&self, reason: String) -> GatewayError {
+        GatewayError::UpstreamUnavailable {
+            upstream: self.upstream.clone(),
+            reason: format!("overloaded: {reason}"),
+            retry_after_secs: Some(1),
+        }
+    }
+}
+
+fn registry() -> &'static Mutex<HashMap<String, Arc<UpstreamQueue>>> {
+    static QUEUES: OnceLock<Mutex<HashMap<String, Arc<UpstreamQueue>>>> = OnceLock::new();
+    QUEUES.get_or_init(Default::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn it_queues_then_admits_when_a_slot_frees() {
+        let q = Arc::new(UpstreamQueue::new(
+            "http://up",
+            1,
+            1,
+            Duration::from_secs(5),
+        ));
+        let first = q.acquire().await.unwrap();
+
+        let waiter = tokio::spawn({
+            let q = q.clone();
+            async move { q.acquire().await.map(|_| ()) }
+        });
+        while q.snapshot().queued == 0 {
+            tokio::task::yield_now().await;
+        }
+        let snap = q.snapshot();
+        assert_eq!((snap.in_flight, snap.queued), (1, 1));
+
+        drop(first);
+        assert!(waiter.await.unwrap().is_ok());
+        let snap = q.snapshot();
+        assert_eq!((snap.in_flight, snap.queued), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn it_rejects_when_the_queue_is_full() {
+        let q = UpstreamQueue::new("http://up", 1, 0, Duration::from_secs(5));
+        let _busy = q.acquire().await.unwrap();
+        match q.acquire().await {
+            Err(GatewayError::UpstreamUnavailable {
+                upstream, reason, ..
+            }) => {
+                assert_eq!(upstream, "http://up");
+                assert!(reason.contains("queue full"), "{reason}");
+            }
+            Err(other) => panic!("expected UpstreamUnavailable, got {other:?}"),
+            Ok(_) => panic!("expected the queue to be full"),
+        }
+        assert_eq!(q.snapshot().queued, 0);
+    }
+
+    #[tokio::test]
+    async fn it_gives_up_after_the_queue_timeout() {
+        let q = UpstreamQueue::new("http://up", 1, 4, Duration::from_millis(20));
+        let _busy = q.acquire().await.unwrap();
+        let err = q.acquire().await.err().expect("wait should time out");
+        assert!(err.to_string().contains("no free slot"), "{err}");
+        assert_eq!(q.snapshot().queued, 0);
+    }
+
+    #[tokio::test]
+    async fn configure_resizes_for_new_requests() {
+        let q = UpstreamQueue::new("http://up", 1, 0, Duration::from_secs(5));
+        let _a = q.acquire().await.unwrap();
+        q.configure(2, 0, Duration::from_secs(5));
+        let _b = q.acquire().await.unwrap();
+        assert_eq!(q.snapshot().in_flight, 2);
+        assert_eq!(q.snapshot().max_concurrent, 2);
+        assert!(q.acquire().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn lowering_the_limit_counts_requests_in_flight() {
+        let q = UpstreamQueue::new("http://up", 3, 0, Duration::from_secs(5));
+        let a = q.acquire().await.unwrap();
+        let b = q.acquire().await.unwrap();
+        q.configure(1, 0, Duration::from_secs(5));
+        // The free slot is retired at once; the two busy ones as they finish.
+        assert!(q.acquire().await.is_err());
+        drop(a);
+        assert!(q.acquire().await.is_err());
+        drop(b);
+        let c = q.acquire().await.unwrap();
+        assert!(q.acquire().await.is_err());
+        drop(c);
+
+        // Raising the limit again first cancels pending retirements.
+        let a = q.acquire().await.unwrap();
+        q.configure(3, 0, Duration::from_secs(5));
+        let b = q.acquire().await.unwrap();
+        q.configure(1, 0, Duration::from_secs(5));
+        q.configure(2, 0, Duration::from_secs(5));
+        drop(a);
+        let _c = q.acquire().await.unwrap();
+        assert!(q.acquire().await.is_err());
+        drop(b);
+        assert!(q.acquire().await.is_ok());
+    }
+
+    #[test]
+    fn shared_queues_are_per_upstream() {
+        let a = UpstreamQueue::for_upstream("http://queue-test-a");
+        let again = UpstreamQueue::for_upstream("http://queue-test-a");
+        assert!(Arc::ptr_eq(&a, &again));
+        let b = UpstreamQueue::for_upstream("http://queue-test-b");
+        assert!(!Arc::ptr_eq(&a, &b));
+    }
+}
@@ -0,0 +1,348 @@
+//! Inbound rate limiting and daily quotas.
+//!
+//! Each client (API key or token subject, else IP address) has a token bucket
+//! refilled at `limits.rate_per_sec` up to `limits.burst`, plus a per-UTC-day
+//! allowance of `limits.daily_quota`. Only `tools/call` requests are charged,
+//! at the cost `RateLimitConfig::cost` assigns them, so a runaway agent is
+//! throttled before it reaches the upstreams.
+
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use axum::body::{to_bytes, Body};
+use axum::extract::{ConnectInfo, Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::{NaiveDate, Utc};
+use lru::LruCache;
+use serde_json::Value;
+
+use crate::core::error::GatewayError;
+use crate::infra::config::RateLimitConfig;
+use crate::infra::http::auth::Principal;
+use crate::infra::http::json as http_json;
+use crate::tools::registry::live::LiveRegistry;
+
+/// Largest request body inspected for `tools/call` costs.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+/// Clients tracked at once; the least recently seen is forgotten beyond this.
+const MAX_CLIENTS: usize = 10_000;
+
+pub struct RateLimiter {
+    clients: Mutex<LruCache<String, Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled: Instant,
+    day: NaiveDate,
+    spent_today: u64,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::with_capacity(MAX_CLIENTS)
+    }
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A limiter tracking at most `clients` callers.
+    pub fn with_capacity(clients: usize) -> Self {
+        let cap = NonZeroUsize::new(clients).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            clients: Mutex::new(LruCache::new(cap)),
+        }
+    }
+
+    /// Charge `cost` to `client`, or say how long to wait before retrying.
+    pub fn check(
+        &self,
+        client: &str,
+        cost: u64,
+        cfg: &RateLimitConfig,
+    ) -> Result<(), GatewayError> {
+        self.check_at(client, cost, cfg, Instant::now(), Utc::now().date_naive())
+    }
+
+    fn check_at(
+        &self,
+        client: &str,
+        cost: u64,
+        cfg: &RateLimitConfig,
+        now: Instant,
+        today: NaiveDate,
+    ) -> Result<(), GatewayError> {
+        if cost == 0 || !cfg.enabled() {
+            return Ok(());
+        }
+        let mut clients = self.clients.lock().expect("rate limiter lock");
+        let bucket = clients.get_or_insert_mut(client.to_string(), || Bucket {
+            tokens: cfg.burst,
+            refilled: now,
+            day: today,
+            spent_today: 0,
+        });
+
+        if bucket.day != today {
+            bucket.day = today;
+            bucket.spent_today = 0;
+        }
+        if cfg.daily_quota > 0 && bucket.spent_today + cost > cfg.daily_quota {
+            return Err(GatewayError::RateLimited {
+                retry_after_secs: secs_until_midnight(),
+            });
+        }
+
+        // A call dearer than the whole bucket may still run, once it is full
+        let charge = if cfg.rate_per_sec > 0.0 {
+            let elapsed = now.duration_since(bucket.refilled).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * cfg.rate_per_sec).min(cfg.burst);
+            bucket.refilled = now;
+            let charge = (cost as f64).min(cfg.burst);
+            if bucket.tokens < charge {
+                let wait = (charge - bucket.tokens) / cfg.rate_per_sec;
+                return Err(GatewayError::RateLimited {
+                    retry_after_secs: wait.ceil().max(1.0) as u64,
+                });
+            }
+            charge
+        } else {
+            bucket.refilled = now;
+            0.0
+        };
+        bucket.tokens -= charge;
+        bucket.spent_today += cost;
+        Ok(())
+    }
+}
+
+fn secs_until_midnight() -> u64 {
+    let now = Utc::now();
+    let midnight = (now.date_naive() + chrono::Days::new(1))
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight exists")
+        .and_utc();
+    (midnight - now).num_seconds().max(1) as u64
+}
+
+/// Who a request is charged to: its authenticated principal, else its peer
+/// address. Session ids are chosen by the client, so they are not used.
+fn client_key(req: &Request) -> String {
+    if let Some(principal) = req.extensions().get::<Principal>() {
+        return format!("key:{}", principal.name);
+    }
+    match req.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+        None => "anonymous".into(),
+    }
+}
+
+/// Total cost of the `tools/call` messages in a (possibly batched) JSON-RPC
+/// body, and the id of the first one.
+fn call_cost(body: &[u8], cfg: &RateLimitConfig) -> (u64, Value) {
+    let messages = match serde_json::from_slice(body) {
+        Ok(Value::Array(batch)) => batch,
+        Ok(single) => vec![single],
+        Err(_) => return (0, Value::Null),
+    };
+    let mut id = None;
+    let mut total = 0;
+    for msg in &messages {
+        let method = msg.get("method").and_then(|m| m.as_str());
+        if !matches!(method, Some("tools/call" | "tools.call")) {
+            continue;
+        }
+        let Some(tool) = msg.pointer("/params/name").and_then(|n| n.as_str()) else {
+            continue;
+        };
+        let args = msg.pointer("/params/arguments").unwrap_or(&Value::Null);
+        total += cfg.cost(tool, args);
+        id.get_or_insert_with(|| msg.get("id").cloned().unwrap_or(Value::Null));
+    }
+    (total, id.unwrap_or(Value::Null))
+}
+
+/// Middleware answering over-limit `tools/call` requests with 429, a
+/// `Retry-After` header and a JSON-RPC rate-limited error. Runs after the
+/// auth layer so limits follow the authenticated caller.
+pub async fn enforce(
+    State((live, limiter)): State<(LiveRegistry, Arc<RateLimiter>)>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let cfg = live.config();
+    if !cfg.limits.enabled() {
+        return next.run(req).await;
+    }
+
+    let client = client_key(&req);
+    let (parts, body) = req.into_parts();
+    let bytes = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return http_json::error_response(
+                Value::Null,
+                GatewayError::InvalidParams(format!("unreadable request body: {e}")),
+            )
+        }
+    };
+    let (cost, id) = call_cost(&bytes, &cfg.limits);
+    if let Err(err) = limiter.check(&client, cost, &cfg.limits) {
+        tracing::warn!(client = %client, cost, error = %err, "request rate limited");
+        return http_json::error_response(id, err);
+    }
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::config::Config;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use axum::Router;
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    fn limits(rate_per_sec: f64, burst: f64, daily_quota: u64) -> RateLimitConfig {
+        RateLimitConfig {
+            rate_per_sec,
+            burst,
+            daily_quota,
+            ..RateLimitConfig::default()
+        }
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    #[test]
+    fn bucket_allows_bursts_then_refills() {
+        let limiter = RateLimiter::new();
+        let cfg = limits(2.0, 3.0, 0);
+        let t0 = Instant::now();
+        for _ in 0..3 {
+            assert!(limiter.check_at("a", 1, &cfg, t0, day(1)).is_ok());
+        }
+        let err = limiter.check_at("a", 1, &cfg, t0, day(1)).unwrap_err();
+        assert_eq!(err.retry_after_secs(), Some(1));
+        // Other clients have their own bucket
+        assert!(limiter.check_at("b", 1, &cfg, t0, day(1)).is_ok());
+
+        let later = t0 + Duration::from_millis(500);
+        assert!(limiter.check_at("a", 1, &cfg, later, day(1)).is_ok());
+        assert!(limiter.check_at("a", 1, &cfg, later, day(1)).is_err());
+    }
+
+    #[test]
+    fn expensive_calls_wait_for_a_full_bucket() {
+        let limiter = RateLimiter::new();
+        let cfg = limits(1.0, 4.0, 0);
+        let t0 = Instant::now();
+        assert!(limiter.check_at("a", 1, &cfg, t0, day(1)).is_ok());
+        let err = limiter.check_at("a", 10, &cfg, t0, day(1)).unwrap_err();
+        assert_eq!(err.retry_after_secs(), Some(1));
+        let full = t0 + Duration::from_secs(1);
+        assert!(limiter.check_at("a", 10, &cfg, full, day(1)).is_ok());
+    }
+
+    #[test]
+    fn daily_quota_resets_at_the_next_day() {
+        let limiter = RateLimiter::new();
+        let cfg = limits(0.0, 20.0, 5);
+        let t0 = Instant::now();
+        assert!(limiter.check_at("a", 3, &cfg, t0, day(1)).is_ok());
+        assert!(limiter.check_at("a", 2, &cfg, t0, day(1)).is_ok());
+        let err = limiter.check_at("a", 1, &cfg, t0, day(1)).unwrap_err();
+        assert!(matches!(err, GatewayError::RateLimited { .. }));
+        assert!(limiter.check_at("a", 5, &cfg, t0, day(2)).is_ok());
+    }
+
+    #[test]
+    fn least_recently_seen_clients_are_forgotten_at_capacity() {
+        let limiter = RateLimiter::with_capacity(2);
+        let cfg = limits(0.0, 20.0, 1);
+        let t0 = Instant::now();
+        assert!(limiter.check_at("a", 1, &cfg, t0, day(1)).is_ok());
+        assert!(limiter.check_at("b", 1, &cfg, t0, day(1)).is_ok());
+        assert!(limiter.check_at("a", 1, &cfg, t0, day(1)).is_err());
+        assert!(limiter.check_at("c", 1, &cfg, t0, day(1)).is_ok());
+        assert_eq!(limiter.clients.lock().unwrap().len(), 2);
+        // "b" was evicted to make room for "c"; "a" is still over quota
+        assert!(limiter.check_at("a", 1, &cfg, t0, day(1)).is_err());
+        assert!(limiter.check_at("b", 1, &cfg, t0, day(1)).is_ok());
+    }
+
+    #[test]
+    fn unauthenticated_clients_are_keyed_by_ip_not_session() {
+        let req = |session: &str| {
+            let mut req = Request::builder()
+                .header("mcp-session-id", session)
+                .body(Body::empty())
+                .unwrap();
+            req.extensions_mut()
+                .insert(ConnectInfo(SocketAddr::from(([192, 0, 2, 7], 40000))));
+            req
+        };
+        assert_eq!(client_key(&req("s1")), "ip:192.0.2.7");
+        assert_eq!(client_key(&req("s2")), "ip:192.0.2.7");
+    }
+
+    #[test]
+    fn only_tool_calls_are_charged() {
+        let cfg = RateLimitConfig::default();
+        let list = br#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#;
+        assert_eq!(call_cost(list, &cfg).0, 0);
+        let batch = format!(
+            r#"[{{"jsonrpc":"2.0","id":7,"method":"tools/call","params":{{"name":"hello.echo"}}}},
+               {{"jsonrpc":"2.0","id":8,"method":"tools/call","params":{{"name":"gael.grammar_check","arguments":{{"text":"{}"}}}}}}]"#,
+            "a".repeat(1500)
+        );
+        assert_eq!(call_cost(batch.as_bytes(), &cfg), (1 + 3, Value::from(7)));
+    }
+
+    #[tokio::test]
+    async fn middleware_returns_429_with_retry_after() {
+        let live = LiveRegistry::new(Config {
+            limits: limits(0.1, 1.0, 0),
+            ..Config::default()
+        });
+        let app = Router::new()
+            .route("/rpc", post(|| async { "ok" }))
+            .route_layer(axum::middleware::from_fn_with_state(
+                (live, Arc::new(RateLimiter::new())),
+                enforce,
+            ));
+        let call = |session: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/rpc")
+                .header("mcp-session-id", session)
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"hello.echo"}}"#,
+                ))
+                .unwrap()
+        };
+        assert_eq!(
+            app.clone().oneshot(call("s1")).await.unwrap().status(),
+            StatusCode::OK
+        );
+
+        // A fresh session id does not buy a fresh bucket
+        let resp = app.clone().oneshot(call("s2")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers()["retry-after"], "10");
+        let bytes = to_bytes(resp.into_body(), 4096).await.unwrap();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["id"], 3);
+        assert_eq!(body["error"]["code"], -32005);
+    }
+}
@@ -0,0 +1,174 @@
+//! Config hot reload. The config file is polled for changes and SIGHUP forces
+//! a re-read; either way the layered config is loaded and validated again and,
+//! if clean, a freshly built registry is swapped into the [`LiveRegistry`].
+//! Loading, validation and the registry build run on the blocking pool, since
+//! they read files and parse dictionaries.
+//!
+//! Settings bound at startup (mode, port, REST route, cache, tracing, logging)
+//! are not re-applied; changing them logs a warning and needs a restart.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::infra::config::{Config, ConfigProblem};
+use crate::tools::registry::live::LiveRegistry;
+
+/// How often the config file's modification time and size are checked.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Watch `path` (if any) and SIGHUP for the lifetime of the process.
+pub fn spawn(path: Option<PathBuf>, live: LiveRegistry) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut hangup = Hangup::new();
+        let mut tick = tokio::time::interval(POLL_INTERVAL);
+        let mut seen = path.as_deref().and_then(fingerprint);
+        loop {
+            let trigger = tokio::select! {
+                _ = hangup.recv() => "SIGHUP",
+                _ = tick.tick() => {
+                    let now = path.as_deref().and_then(fingerprint);
+                    if now == seen {
+                        continue;
+                    }
+                    seen = now;
+                    "config file changed"
+                }
+            };
+            match reload(path.as_deref(), &live).await {
+                Ok(changed) => {
+                    tracing::info!(trigger, tools_changed = changed, "config reloaded")
+                }
+                Err(problems) => {
+                    for problem in &problems {
+                        tracing::warn!(trigger, %problem, "config reload rejected");
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Load and validate the config at `path`, then swap it into `live`. Returns
+/// whether the tool set changed; an invalid config leaves `live` untouched.
+pub async fn reload(path: Option<&Path>, live: &LiveRegistry) -> Result<bool, Vec<ConfigProblem>> {
+    let path = path.map(Path::to_path_buf);
+    let cfg = tokio::task::spawn_blocking(move || Config::load_valid(path.as_deref()))
+        .await
+        .expect("config loading panicked")?;
+    warn_restart_only(&live.config(), &cfg);
+    Ok(live.reload(cfg).await)
+}
+
+fn warn_restart_only(old: &Config, new: &Config) {
+    let fixed = [
+        ("server.mode", old.mode != new.mode),
+        ("server.port", old.port != new.port),
+        (
+            "server.deprecate_rest",
+            old.deprecate_rest != new.deprecate_rest,
+        ),
+        (
+            "cache",
+            (old.cache_capacity, old.cache_ttl, &old.cache_path)
+                != (new.cache_capacity, new.cache_ttl, &new.cache_path),
+        ),
+        ("tracing", old.tracing != new.tracing),
+        ("logging", old.logging != new.logging),
+    ];
+    for (field, changed) in fixed {
+        if changed {
+            tracing::warn!(
+                field,
+                "setting changed but only takes effect after a restart"
+            );
+        }
+    }
+}
+
+fn fingerprint(path: &Path) -> Option<(SystemTime, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+/// SIGHUP listener; never fires where the signal does not exist.
+struct Hangup {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl Hangup {
+    fn new() -> Self {
+        Self {
+            #[cfg(unix)]
+            signal: tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+                .map_err(|e| tracing::warn!(error = %e, "cannot listen for SIGHUP"))
+                .ok(),
+        }
+    }
+
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(signal) = &mut self.signal {
+            if signal.recv().await.is_some() {
+                return;
+            }
+        }
+        std::future::pending::<()>().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    fn temp_config(name: &str, body: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "irish-mcp-reload-{}-{name}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, body).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn reload_applies_a_valid_file() {
+        let path = temp_config("valid", "");
+        let live = LiveRegistry::new(Config::load(Some(&path)).unwrap());
+        assert!(live.registry().0.contains_key("hello.echo"));
+
+        std::fs::write(&path, "[tools]\ndeny = [\"hello.echo\"]\n").unwrap();
+        assert_eq!(reload(Some(&path), &live).await, Ok(true));
+        assert!(!live.registry().0.contains_key("hello.echo"));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn reload_keeps_current_registry_on_invalid_config() {
+        let path = temp_config("invalid", "");
+        let live = LiveRegistry::new(Config::load(Some(&path)).unwrap());
+
+        std::fs::write(&path, "[tools]\ndeny = [\"hello.wave\"]\n").unwrap();
+        let problems = reload(Some(&path), &live).await.unwrap_err();
+        assert_eq!(problems[0].field, "tools.deny");
+
+        std::fs::write(&path, "[tools\n").unwrap();
+        let problems = reload(Some(&path), &live).await.unwrap_err();
+        assert_eq!(problems[0].field, "config");
+
+        assert!(live.registry().0.contains_key("hello.echo"));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn fingerprint_tracks_file_changes() {
+        let path = temp_config("fingerprint", "a");
+        let before = fingerprint(&path);
+        std::fs::write(&path, "ab").unwrap();
+        assert_ne!(before, fingerprint(&path));
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(fingerprint(&path), None);
+    }
+}
@@ -0,0 +1,203 @@
+//! OpenTelemetry tracing and request correlation.
+//!
+//! `tracing` spans are bridged to OpenTelemetry so a call shows up as one
+//! trace: HTTP request → MCP method → tool call → upstream call. W3C trace
+//! context arrives in `traceparent` and is passed on to the upstreams, as is
+//! the caller's `x-request-id`.
+//!
+//! The request id rides in a task-local, so work spawned onto other tasks
+//! must go through [`propagate`] to keep it (and its parent span).
+
+use std::future::Future;
+
+use opentelemetry::propagation::{Extractor, Injector, TextMapPropagator};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::Context;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, SdkTracer, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use tracing::Instrument;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+
+use crate::infra::config::TracingConfig;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Build the tracer provider `cfg` asks for. With the `none` exporter spans
+/// are still created, so trace ids propagate, but nothing is exported.
+pub fn tracer_provider(cfg: &TracingConfig) -> Result<SdkTracerProvider, String> {
+    let builder = SdkTracerProvider::builder()
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            cfg.sample_ratio,
+        ))))
+        .with_resource(
+            Resource::builder()
+                .with_service_name(cfg.service_name.clone())
+                .build(),
+        );
+    let builder = match cfg.exporter.as_str() {
+        "none" => builder,
+        "stdout" => builder.with_simple_exporter(opentelemetry_stdout::SpanExporter::default()),
+        "otlp" => {
+            let mut exporter = opentelemetry_otlp::SpanExporter::builder().with_http();
+            if let Some(base) = &cfg.otlp_endpoint {
+                exporter =
+                    exporter.with_endpoint(format!("{}/v1/traces", base.trim_end_matches('/')));
+            }
+            let exporter = exporter
+                .build()
+                .map_err(|e| format!("cannot build OTLP exporter: {e}"))?;
+            builder.with_batch_exporter(exporter)
+        }
+        other => return Err(format!("unknown trace exporter '{other}'")),
+    };
+    Ok(builder.build())
+}
+
+/// `tracing` layer recording spans into `provider`.
+pub fn layer<S>(provider: &SdkTracerProvider) -> OpenTelemetryLayer<S, SdkTracer>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(env!("CARGO_PKG_NAME")))
+}
+
+/// The inbound request id of the call being handled, if any.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Run `fut` with `id` as the current request id.
+pub async fn with_request_id<F: Future>(id: String, fut: F) -> F::Output {
+    REQUEST_ID.scope(id, fut).await
+}
+
+/// Carry the current span and request id into `fut`, for handing it to
+/// `tokio::spawn` or a `JoinSet`.
+pub fn propagate<F: Future>(fut: F) -> impl Future<Output = F::Output> {
+    let fut = fut.instrument(tracing::Span::current());
+    let id = current_request_id();
+    async move {
+        match id {
+            Some(id) => REQUEST_ID.scope(id, fut).await,
+            None => fut.await,
+        }
+    }
+}
+
+/// Trace context sent by the caller in `traceparent`/`tracestate`.
+pub fn extract(headers: &axum::http::HeaderMap) -> Context {
+    TraceContextPropagator::new().extract(&HeaderExtractor(headers))
+}
+
+/// Add `traceparent` (and `tracestate`) for the current span to an upstream request.
+pub fn inject(builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    let mut fields = HeaderInjector(Vec::new());
+    TraceContextPropagator::new().inject_context(&tracing::Span::current().context(), &mut fields);
+    fields
+        .0
+        .into_iter()
+        .fold(builder, |b, (name, value)| b.header(name, value))
+}
+
+struct HeaderExtractor<'a>(&'a axum::http::HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+}
+
+struct HeaderInjector(Vec<(String, String)>);
+
+impl Injector for HeaderInjector {
+    fn set(&mut self, key: &str, value: String) {
+        self.0.push((key.to_string(), value));
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use opentelemetry_sdk::trace::InMemorySpanExporter;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// A subscriber exporting spans to memory, for asserting on traces.
+    pub(crate) fn recording() -> (
+        impl tracing::Subscriber + Send + Sync,
+        SdkTracerProvider,
+        InMemorySpanExporter,
+    ) {
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber = tracing_subscriber::registry().with(layer(&provider));
+        (subscriber, provider, exporter)
+    }
+
+    #[test]
+    fn every_exporter_builds() {
+        for exporter in ["none", "stdout", "otlp"] {
+            let cfg = TracingConfig {
+                exporter: exporter.into(),
+                otlp_endpoint: Some("http://127.0.0.1:4318".into()),
+                ..TracingConfig::default()
+            };
+            let provider = tracer_provider(&cfg).unwrap();
+            let _ = provider.shutdown();
+        }
+        let bad = TracingConfig {
+            exporter: "jaeger".into(),
+            ..TracingConfig::default()
+        };
+        assert!(tracer_provider(&bad).is_err());
+    }
+
+    #[tokio::test]
+    async fn inbound_trace_context_reaches_upstream_requests() {
+        let (subscriber, _provider, _exporter) = recording();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                .parse()
+                .unwrap(),
+        );
+        let span = tracing::info_span!("http.request");
+        span.set_parent(extract(&headers));
+
+        let req = span.in_scope(|| inject(reqwest::Client::new().get("http://upstream")));
+        let req = req.build().unwrap();
+        let traceparent = req.headers()["traceparent"].to_str().unwrap();
+        assert!(
+            traceparent.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"),
+            "{traceparent}"
+        );
+        assert!(
+            !traceparent.contains("00f067aa0ba902b7"),
+            "new span id expected"
+        );
+    }
+
+    #[tokio::test]
+    async fn request_id_follows_spawned_work() {
+        assert_eq!(current_request_id(), None);
+        let seen = with_request_id("req-42".into(), async {
+            tokio::spawn(propagate(async { current_request_id() }))
+                .await
+                .unwrap()
+        })
+        .await;
+        assert_eq!(seen.as_deref(), Some("req-42"));
+    }
+}
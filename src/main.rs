@@ -6,20 +6,31 @@ mod domain;
 mod infra;
 mod tools;
 
+use clap::Parser;
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    infra::logging::init();
+    let args = cli::Cli::parse();
 
     // Check if we're running admin commands
     // TODO(refactor-fit-and-finish): Consider feature-gating CLI entry for smaller prod binary.
-    if should_run_cli(std::env::args().len()) {
-        let exit_code = cli::run().await;
+    if let Some(command) = args.command {
+        infra::logging::init();
+        let exit_code = cli::run(command, args.config.as_deref()).await;
         PROCESS_EXITER.exit(map_exit(exit_code));
     }
-    infra::boot::run_server().await
+    // Refuse to start on a config that a reload would reject
+    let cfg = infra::config::Config::load_valid(args.config.as_deref()).map_err(|problems| {
+        let report: Vec<String> = problems.iter().map(|p| format!("  {p}")).collect();
+        anyhow::anyhow!("invalid config:\n{}", report.join("\n"))
+    })?;
+    // Held until exit so buffered spans are flushed
+    let _tracing = infra::logging::init_server(&cfg).map_err(anyhow::Error::msg)?;
+    infra::boot::run_server(cfg, args.config).await
 }
 
 #[inline]
+#[allow(dead_code)]
 fn should_run_cli(arg_len: usize) -> bool {
     arg_len > 1
 }
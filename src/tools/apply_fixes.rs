@@ -0,0 +1,377 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::clients::gramadoir::GramadoirRemote;
+use crate::core::error::GatewayError;
+use crate::core::tool::{Tool, ToolSpec};
+use crate::domain::{GrammarIssue, SpellCorrection};
+use crate::tools::grammar::rules;
+
+/// One-shot proofreading: applies the top grammar and spelling suggestions.
+#[derive(Clone)]
+pub struct ApplyFixesTool {
+    grammar: GramadoirRemote,
+    spellcheck: Option<Arc<dyn Tool>>,
+    disabled_rules: Vec<String>,
+}
+
+impl ApplyFixesTool {
+    pub fn new(grammar: GramadoirRemote, spellcheck: Option<Arc<dyn Tool>>) -> Self {
+        Self {
+            grammar,
+            spellcheck,
+            disabled_rules: Vec::new(),
+        }
+    }
+
+    /// Grammar rules never applied unless a call enables them, as for `gael.grammar_check`.
+    pub fn with_disabled_rules(mut self, rules: Vec<String>) -> Self {
+        self.disabled_rules = rules;
+        self
+    }
+
+    async fn spelling(&self, text: &str) -> Result<Vec<SpellCorrection>, GatewayError> {
+        let Some(spellcheck) = &self.spellcheck else {
+            return Ok(Vec::new());
+        };
+        let out = spellcheck.call(&json!({ "text": text })).await?;
+        serde_json::from_value(out["corrections"].clone())
+            .map_err(|e| GatewayError::Message(e.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EditSource {
+    Grammar,
+    Spelling,
+}
+
+/// A replacement applied to the input. Offsets are chars into the original text.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Edit {
+    pub start: usize,
+    pub end: usize,
+    pub original: String,
+    pub replacement: String,
+    pub source: EditSource,
+    /// Grammar rule id, or the spelling reason (`unknown-word`, `missing-fada`).
+    pub code: String,
+}
+
+/// Pick non-overlapping edits (earliest first, grammar before spelling on ties)
+/// and apply them. Returns the corrected text and the edits that were applied.
+fn apply_edits(text: &str, mut candidates: Vec<Edit>) -> (String, Vec<Edit>) {
+    let chars: Vec<char> = text.chars().collect();
+    candidates.retain(|e| e.start < e.end && e.end <= chars.len());
+    candidates.sort_by_key(|e| (e.start, e.source != EditSource::Grammar, e.end));
+
+    let mut applied: Vec<Edit> = Vec::new();
+    for mut edit in candidates {
+        if applied.last().is_some_and(|prev| edit.start < prev.end) {
+            continue;
+        }
+        edit.original = chars[edit.start..edit.end].iter().collect();
+        if edit.original != edit.replacement {
+            applied.push(edit);
+        }
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut cursor = 0;
+    for edit in &applied {
+        out.extend(&chars[cursor..edit.start]);
+        out.push_str(&edit.replacement);
+        cursor = edit.end;
+    }
+    out.extend(&chars[cursor..]);
+    (out, applied)
+}
+
+fn grammar_edit(issue: GrammarIssue) -> Option<Edit> {
+    let replacement = issue.suggestions.into_iter().next()?;
+    Some(Edit {
+        start: issue.start,
+        end: issue.end,
+        original: String::new(),
+        replacement,
+        source: EditSource::Grammar,
+        code: issue.code,
+    })
+}
+
+fn spelling_edit(correction: SpellCorrection) -> Option<Edit> {
+    let code = correction.reason.as_str().to_string();
+    let replacement = correction.suggestions.into_iter().next()?.word;
+    Some(Edit {
+        start: correction.start,
+        end: correction.end,
+        original: String::new(),
+        replacement,
+        source: EditSource::Spelling,
+        code,
+    })
+}
+
+impl ToolSpec for ApplyFixesTool {
+    fn name(&self) -> &'static str {
+        "gael.apply_fixes"
+    }
+    fn description(&self) -> &'static str {
+        "Apply the top Gramadóir and spellcheck suggestions and return the corrected text with its edits"
+    }
+    fn input_schema(&self) -> serde_json::Value {
+        let mut properties = rules::schema_properties();
+        properties["text"] = json!({ "type": "string" });
+        properties["codes"] = json!({
+            "type": "array",
+            "items": { "type": "string" },
+            "description": "Only apply edits whose grammar rule id or spelling reason is listed"
+        });
+        properties["spelling"] = json!({ "type": "boolean", "default": true });
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": ["text"]
+        })
+    }
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "text": { "type": "string" },
+                "edits": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "start": { "type": "integer", "minimum": 0 },
+                            "end": { "type": "integer", "minimum": 0 },
+                            "original": { "type": "string" },
+                            "replacement": { "type": "string" },
+                            "source": { "type": "string", "enum": ["grammar", "spelling"] },
+                            "code": { "type": "string" }
+                        },
+                        "required": ["start", "end", "original", "replacement", "source", "code"]
+                    }
+                }
+            },
+            "required": ["text", "edits"]
+        }))
+    }
+}
+
+#[async_trait]
+impl Tool for ApplyFixesTool {
+    async fn call(&self, arguments: &serde_json::Value) -> Result<serde_json::Value, GatewayError> {
+        let Some(text) = arguments.get("text").and_then(|v| v.as_str()) else {
+            return Err(GatewayError::InvalidParams("missing 'text'".into()));
+        };
+        let filter = rules::RuleFilter::from_args(arguments, &self.disabled_rules)
+            .map_err(GatewayError::InvalidParams)?;
+        let codes = match arguments.get("codes") {
+            None | Some(serde_json::Value::Null) => None,
+            Some(_) => {
+                Some(rules::string_list(arguments, "codes").map_err(GatewayError::InvalidParams)?)
+            }
+        };
+        let spelling = arguments
+            .get("spelling")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        let (issues, corrections) = if spelling {
+            tokio::try_join!(self.grammar.analyze(text), self.spelling(text))?
+        } else {
+            (self.grammar.analyze(text).await?, Vec::new())
+        };
+
+        let candidates: Vec<Edit> = filter
+            .apply(issues)
+            .into_iter()
+            .filter_map(grammar_edit)
+            .chain(corrections.into_iter().filter_map(spelling_edit))
+            .filter(|e| {
+                codes
+                    .as_ref()
+                    .is_none_or(|c| c.iter().any(|code| rules::rule_matches(code, &e.code)))
+            })
+            .collect();
+        let (corrected, edits) = apply_edits(text, candidates);
+        Ok(json!({ "text": corrected, "edits": edits }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::hello::HelloTool;
+    use httpmock::prelude::*;
+
+    fn edit(start: usize, end: usize, replacement: &str, source: EditSource) -> Edit {
+        Edit {
+            start,
+            end,
+            original: String::new(),
+            replacement: replacement.into(),
+            source,
+            code: "X".into(),
+        }
+    }
+
+    #[test]
+    fn apply_edits_skips_overlaps_and_prefers_grammar() {
+        let text = "Tá an cota ar an bord";
+        let (out, applied) = apply_edits(
+            text,
+            vec![
+                edit(14, 21, "an mbord", EditSource::Spelling),
+                edit(6, 10, "cóta", EditSource::Spelling),
+                edit(14, 21, "an mbord", EditSource::Grammar),
+                edit(11, 21, "ar an mbord", EditSource::Grammar),
+            ],
+        );
+        assert_eq!(out, "Tá an cóta ar an mbord");
+        assert_eq!(applied.len(), 2);
+        assert_eq!(applied[1].original, "ar an bord");
+        assert_eq!(applied[1].source, EditSource::Grammar);
+    }
+
+    #[test]
+    fn apply_edits_ignores_out_of_range_and_noop() {
+        let (out, applied) = apply_edits(
+            "abc",
+            vec![
+                edit(1, 9, "x", EditSource::Grammar),
+                edit(0, 1, "a", EditSource::Grammar),
+            ],
+        );
+        assert_eq!(out, "abc");
+        assert!(applied.is_empty());
+    }
+
+    fn mock_gramadoir(server: &MockServer) {
+        server.mock(|when, then| {
+            when.method(POST).path("/api/gramadoir/1.0");
+            then.status(200).json_body(json!([{
+                "context":"Tá an cota ar an bord","contextoffset":"11","errorlength":"10",
+                "fromx":"11","fromy":"0","toy":"0","tox":"20",
+                "msg":"Eclipsis missing","ruleId":"CLAOCHLU"
+            }]));
+        });
+    }
+
+    #[tokio::test]
+    async fn it_applies_grammar_and_spelling_fixes() {
+        use crate::tools::spellcheck::hunspell::{tests as fixture, Dictionary};
+        use crate::tools::spellcheck::SpellcheckLocalBackend;
+
+        let server = MockServer::start();
+        mock_gramadoir(&server);
+        let dict = Dictionary::parse(fixture::AFF, fixture::DIC).unwrap();
+        let spell: Arc<dyn Tool> = Arc::new(SpellcheckLocalBackend::with_dictionary(dict));
+        let tool = ApplyFixesTool::new(GramadoirRemote::new(server.base_url()), Some(spell));
+
+        let out = tool
+            .call(&json!({"text":"Tá an cota ar an bord"}))
+            .await
+            .unwrap();
+        assert_eq!(out["text"], "Tá an cóta ar an mbord");
+        assert_eq!(out["edits"][0]["source"], "spelling");
+        assert_eq!(out["edits"][0]["code"], "missing-fada");
+        assert_eq!(out["edits"][1]["code"], "CLAOCHLU");
+
+        let out = tool
+            .call(&json!({"text":"Tá an cota ar an bord","codes":["missing-fada"]}))
+            .await
+            .unwrap();
+        assert_eq!(out["text"], "Tá an cóta ar an bord");
+
+        let out = tool
+            .call(&json!({"text":"Tá an cota ar an bord","spelling":false}))
+            .await
+            .unwrap();
+        assert_eq!(out["text"], "Tá an cota ar an mbord");
+    }
+
+    #[tokio::test]
+    async fn it_skips_disabled_grammar_rules() {
+        let server = MockServer::start();
+        mock_gramadoir(&server);
+        let tool = ApplyFixesTool::new(GramadoirRemote::new(server.base_url()), None)
+            .with_disabled_rules(vec!["CLAOCHLU".into()]);
+
+        let out = tool
+            .call(&json!({"text":"Tá an cota ar an bord"}))
+            .await
+            .unwrap();
+        assert_eq!(out["text"], "Tá an cota ar an bord");
+        assert!(out["edits"].as_array().unwrap().is_empty());
+
+        let out = tool
+            .call(&json!({"text":"Tá an cota ar an bord","enable_rules":["CLAOCHLU"]}))
+            .await
+            .unwrap();
+        assert_eq!(out["text"], "Tá an cota ar an mbord");
+    }
+
+    #[tokio::test]
+    async fn it_matches_codes_by_short_rule_id() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/api/gramadoir/1.0");
+            then.status(200).json_body(json!([{
+                "context":"Tá an cota ar an bord","contextoffset":"11","errorlength":"10",
+                "fromx":"11","fromy":"0","toy":"0","tox":"20",
+                "msg":"Eclipsis missing","ruleId":"Lingua::GA::Gramadoir/CLAOCHLU"
+            }]));
+        });
+        let tool = ApplyFixesTool::new(GramadoirRemote::new(server.base_url()), None);
+
+        let out = tool
+            .call(&json!({"text":"Tá an cota ar an bord","codes":["CLAOCHLU"]}))
+            .await
+            .unwrap();
+        assert_eq!(out["text"], "Tá an cota ar an mbord");
+        assert_eq!(out["edits"][0]["code"], "Lingua::GA::Gramadoir/CLAOCHLU");
+    }
+
+    #[tokio::test]
+    async fn it_rejects_malformed_codes() {
+        let tool = ApplyFixesTool::new(GramadoirRemote::new("http://localhost:0"), None);
+        for codes in [json!("CLAOCHLU"), json!(["CLAOCHLU", 1])] {
+            let err = tool
+                .call(&json!({"text":"x","codes":codes}))
+                .await
+                .unwrap_err();
+            assert!(matches!(err, GatewayError::InvalidParams(_)), "{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn it_surfaces_spellcheck_shape_errors() {
+        let server = MockServer::start();
+        mock_gramadoir(&server);
+        let not_spell: Arc<dyn Tool> = Arc::new(HelloTool);
+        let tool = ApplyFixesTool::new(GramadoirRemote::new(server.base_url()), Some(not_spell));
+        assert!(tool.call(&json!({"text":"x"})).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn it_validates_missing_text() {
+        let tool = ApplyFixesTool::new(GramadoirRemote::new("http://localhost:0"), None);
+        let err = tool.call(&json!({})).await.unwrap_err();
+        assert!(err.to_string().contains("missing 'text'"));
+    }
+
+    #[test]
+    fn spec_fields_present() {
+        let t = ApplyFixesTool::new(GramadoirRemote::new("http://x"), None);
+        assert_eq!(t.name(), "gael.apply_fixes");
+        assert!(t.input_schema()["properties"]["codes"].is_object());
+        assert!(t.output_schema().unwrap()["properties"]["edits"].is_object());
+    }
+}
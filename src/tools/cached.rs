@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::error::GatewayError;
+use crate::core::tool::{Tool, ToolSpec};
+use crate::infra::runtime::cache::ResultCache;
+
+/// Serves repeated calls with identical arguments from a [`ResultCache`].
+/// Errors are never cached.
+pub struct CachedTool {
+    inner: Arc<dyn Tool>,
+    cache: Arc<ResultCache>,
+    /// Identifies what answers the calls (e.g. dictionary path or upstream URL),
+    /// so results from one backend are never served for another.
+    backend: String,
+}
+
+impl CachedTool {
+    pub fn new(inner: Arc<dyn Tool>, cache: Arc<ResultCache>, backend: impl Into<String>) -> Self {
+        Self {
+            inner,
+            cache,
+            backend: backend.into(),
+        }
+    }
+}
+
+impl ToolSpec for CachedTool {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+    fn description(&self) -> &'static str {
+        self.inner.description()
+    }
+    fn input_schema(&self) -> serde_json::Value {
+        self.inner.input_schema()
+    }
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        self.inner.output_schema()
+    }
+}
+
+#[async_trait]
+impl Tool for CachedTool {
+    async fn call(&self, arguments: &serde_json::Value) -> Result<serde_json::Value, GatewayError> {
+        // serde_json objects are key-sorted, so equal arguments serialise identically.
+        let key = ResultCache::key(self.inner.name(), &[&self.backend, &arguments.to_string()]);
+        if let Some(hit) = self.cache.get(&key) {
+            return Ok(hit);
+        }
+        let out = self.inner.call(arguments).await?;
+        self.cache.insert(key, out.clone());
+        Ok(out)
+    }
+
+    async fn health(&self) -> bool {
+        self.inner.health().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::spellcheck::SpellcheckRemoteBackend;
+    use httpmock::prelude::*;
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn it_serves_repeat_calls_from_cache() {
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(POST).path("/api/spellcheck/1.0");
+            then.status(200).json_body(json!({"corrections": []}));
+        });
+        let cache = Arc::new(ResultCache::new(8, Duration::from_secs(60)));
+        let tool = CachedTool::new(
+            Arc::new(SpellcheckRemoteBackend::new(server.base_url())),
+            cache.clone(),
+            server.base_url(),
+        );
+
+        assert_eq!(tool.name(), "gael.spellcheck.v1");
+        for _ in 0..3 {
+            let out = tool.call(&json!({"text":"Dia duit"})).await.unwrap();
+            assert!(out["corrections"].as_array().unwrap().is_empty());
+        }
+        m.assert_hits(1);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 1));
+    }
+
+    #[tokio::test]
+    async fn it_keys_results_on_the_backend() {
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(POST).path("/api/spellcheck/1.0");
+            then.status(200).json_body(json!({"corrections": []}));
+        });
+        let cache = Arc::new(ResultCache::new(8, Duration::from_secs(60)));
+        let inner: Arc<dyn Tool> = Arc::new(SpellcheckRemoteBackend::new(server.base_url()));
+        let first = CachedTool::new(inner.clone(), cache.clone(), "remote:a");
+        let second = CachedTool::new(inner, cache.clone(), "remote:b");
+
+        first.call(&json!({"text":"Dia duit"})).await.unwrap();
+        second.call(&json!({"text":"Dia duit"})).await.unwrap();
+        m.assert_hits(2);
+        assert_eq!(cache.stats().entries, 2);
+    }
+
+    #[tokio::test]
+    async fn it_does_not_cache_errors() {
+        let tool = CachedTool::new(
+            Arc::new(SpellcheckRemoteBackend::new("http://example")),
+            Arc::new(ResultCache::new(8, Duration::from_secs(60))),
+            "http://example",
+        );
+        assert!(tool.call(&json!({})).await.is_err());
+        assert_eq!(tool.cache.stats().entries, 0);
+    }
+}
@@ -1,13 +1,22 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use serde_json::json;
 
 use crate::clients::gramadoir::GramadoirRemote;
+use crate::core::error::GatewayError;
 use crate::core::tool::{Tool, ToolSpec};
+use crate::domain::GrammarIssue;
+use crate::infra::config::UpstreamConfig;
+use crate::infra::runtime::cache::ResultCache;
 
 #[allow(dead_code)]
 #[derive(Clone)]
 pub struct GrammarTool {
     client: GramadoirRemote,
+    disabled_rules: Vec<String>,
+    batch_concurrency: usize,
+    batch_max_texts: usize,
 }
 
 impl GrammarTool {
@@ -15,11 +24,87 @@ impl GrammarTool {
     pub fn new(base_url: impl Into<String>) -> Self {
         Self {
             client: GramadoirRemote::new(base_url),
+            disabled_rules: Vec::new(),
+            batch_concurrency: batch::DEFAULT_CONCURRENCY,
+            batch_max_texts: batch::DEFAULT_MAX_TEXTS,
         }
     }
+
+    /// Rule ids suppressed unless a call lists them in `enable_rules`.
+    pub fn with_disabled_rules(mut self, rules: Vec<String>) -> Self {
+        self.disabled_rules = rules;
+        self
+    }
+
+    /// Share a result cache for upstream Gramadóir calls.
+    pub fn with_cache(mut self, cache: Option<Arc<ResultCache>>) -> Self {
+        self.client = self.client.with_cache(cache);
+        self
+    }
+
+    /// Apply Gramadóir timeout, retry, pool and HTTP/2 settings.
+    pub fn with_upstream(mut self, cfg: &UpstreamConfig) -> Self {
+        self.client = self.client.with_upstream(cfg);
+        self
+    }
+
+    /// Upstream requests in flight for a `texts` batch.
+    pub fn with_batch_concurrency(mut self, concurrency: usize) -> Self {
+        self.batch_concurrency = concurrency;
+        self
+    }
+
+    /// Most texts accepted in one `texts` batch.
+    pub fn with_batch_max_texts(mut self, max: usize) -> Self {
+        self.batch_max_texts = max;
+        self
+    }
 }
 
-pub mod tool_router;
+pub mod batch;
+pub mod rules;
+
+/// Input schema of `gael.grammar_check`.
+/// Either `text` (single) or `texts` (batch) is required.
+pub fn input_schema() -> serde_json::Value {
+    let mut properties = rules::schema_properties();
+    properties["text"] = json!({ "type": "string" });
+    properties["texts"] = json!({
+        "type": "array",
+        "items": { "type": "string" },
+        "description": "Check many texts in one call; results keep input order"
+    });
+    json!({
+      "type": "object",
+      "properties": properties,
+      "anyOf": [{ "required": ["text"] }, { "required": ["texts"] }]
+    })
+}
+
+/// `{"issues": [...]}` for `text`, or `{"results": [{"issues": [...]} | {"error": "..."}]}`
+/// for `texts`.
+pub fn output_schema() -> serde_json::Value {
+    let mut schema = GrammarIssue::result_schema();
+    let issues = schema["properties"]["issues"].clone();
+    schema["properties"]["results"] = json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": {
+                "issues": issues,
+                "error": { "type": "string" }
+            }
+        }
+    });
+    if let Some(obj) = schema.as_object_mut() {
+        obj.remove("required");
+        obj.insert(
+            "anyOf".into(),
+            json!([{ "required": ["issues"] }, { "required": ["results"] }]),
+        );
+    }
+    schema
+}
 
 impl ToolSpec for GrammarTool {
     fn name(&self) -> &'static str {
@@ -29,22 +114,30 @@ impl ToolSpec for GrammarTool {
         "Irish grammar/spell check via Gramadóir"
     }
     fn input_schema(&self) -> serde_json::Value {
-        json!({
-          "type":"object",
-          "properties": { "text": { "type":"string" } },
-          "required": ["text"]
-        })
+        input_schema()
+    }
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        Some(output_schema())
     }
 }
 
 #[async_trait]
 impl Tool for GrammarTool {
-    async fn call(&self, arguments: &serde_json::Value) -> Result<serde_json::Value, String> {
+    async fn call(&self, arguments: &serde_json::Value) -> Result<serde_json::Value, GatewayError> {
+        let filter = rules::RuleFilter::from_args(arguments, &self.disabled_rules)
+            .map_err(GatewayError::InvalidParams)?;
+        if let Some(texts) = batch::texts_from_args(arguments, self.batch_max_texts)
+            .map_err(GatewayError::InvalidParams)?
+        {
+            let results =
+                batch::analyze_batch(&self.client, texts, self.batch_concurrency, &filter).await;
+            return Ok(json!({ "results": results }));
+        }
         let Some(text) = arguments.get("text").and_then(|v| v.as_str()) else {
-            return Err("missing 'text'".to_string());
+            return Err(GatewayError::InvalidParams("missing 'text'".into()));
         };
-        let issues = self.client.analyze(text).await.map_err(|e| e.to_string())?;
-        Ok(json!({ "issues": issues }))
+        let issues = self.client.analyze(text).await?;
+        Ok(json!({ "issues": filter.apply(issues) }))
     }
 }
 
@@ -81,6 +174,69 @@ mod tests {
         assert_eq!(out["issues"][0]["code"], "SPELL");
     }
 
+    #[tokio::test]
+    async fn it_filters_rules_from_config_and_arguments() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/api/gramadoir/1.0");
+            then.status(200).json_body(json!([
+                {"msg":"Loanword","ruleId":"Lingua::GA::Gramadoir/IASACHT","fromx":"0","errorlength":"2"},
+                {"msg":"Eclipsis missing","ruleId":"Lingua::GA::Gramadoir/CLAOCHLU","fromx":"3","errorlength":"2"}
+            ]));
+        });
+
+        let tool = GrammarTool::new(server.base_url()).with_disabled_rules(vec!["IASACHT".into()]);
+        let out = tool.call(&json!({"text":"ab cd"})).await.unwrap();
+        assert_eq!(out["issues"].as_array().unwrap().len(), 1);
+        assert_eq!(out["issues"][0]["code"], "Lingua::GA::Gramadoir/CLAOCHLU");
+
+        let out = tool
+            .call(&json!({"text":"ab cd","enable_rules":["IASACHT"]}))
+            .await
+            .unwrap();
+        assert_eq!(out["issues"].as_array().unwrap().len(), 1);
+        assert_eq!(out["issues"][0]["code"], "Lingua::GA::Gramadoir/IASACHT");
+
+        let out = tool
+            .call(&json!({"text":"ab cd","disable_rules":["CLAOCHLU"]}))
+            .await
+            .unwrap();
+        assert!(out["issues"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_checks_texts_in_batch() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/api/gramadoir/1.0");
+            then.status(200).json_body(json!([
+                {"msg":"Loanword","ruleId":"IASACHT","fromx":"0","errorlength":"1"}
+            ]));
+        });
+
+        let tool = GrammarTool::new(server.base_url()).with_batch_concurrency(1);
+        let out = tool.call(&json!({"texts":["a","b","c"]})).await.unwrap();
+        let results = out["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|r| r["issues"][0]["code"] == "IASACHT"));
+
+        let out = tool
+            .call(&json!({"texts":["a"],"disable_rules":["IASACHT"]}))
+            .await
+            .unwrap();
+        assert!(out["results"][0]["issues"].as_array().unwrap().is_empty());
+
+        let err = tool.call(&json!({"texts":"a"})).await.unwrap_err();
+        assert!(err.to_string().contains("'texts'"));
+
+        let tool = tool.with_batch_max_texts(2);
+        let err = tool
+            .call(&json!({"texts":["a","b","c"]}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GatewayError::InvalidParams(_)));
+    }
+
     #[tokio::test]
     async fn it_validates_missing_text() {
         let tool = GrammarTool::new("http://localhost:0");
@@ -96,6 +252,12 @@ mod tests {
         let s = t.input_schema();
         assert_eq!(s["type"], "object");
         assert!(s["properties"]["text"].is_object());
+        assert!(s["properties"]["enable_rules"].is_object());
+        assert!(s["properties"]["disable_rules"].is_object());
+        assert!(s["properties"]["texts"].is_object());
+        let out = t.output_schema().unwrap();
+        assert!(out["properties"]["issues"].is_object());
+        assert!(out["properties"]["results"]["items"]["properties"]["issues"].is_object());
     }
 
     #[tokio::test]
@@ -110,6 +272,6 @@ mod tests {
 
         let tool = GrammarTool::new(server.base_url());
         let err = tool.call(&json!({"text":"X"})).await.unwrap_err();
-        assert!(err.contains("status"));
+        assert!(err.to_string().contains("status"));
     }
 }
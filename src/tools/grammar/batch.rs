@@ -0,0 +1,150 @@
+//! Batch grammar checking: many texts in one call with bounded concurrency.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::clients::gramadoir::GramadoirRemote;
+use crate::domain::GrammarIssue;
+use crate::infra::telemetry;
+use crate::tools::grammar::rules::RuleFilter;
+
+/// Upstream requests in flight per batch when not configured.
+pub const DEFAULT_CONCURRENCY: usize = 8;
+/// Most texts accepted in one batch when not configured.
+pub const DEFAULT_MAX_TEXTS: usize = 100;
+
+/// Result for one input text; failures are reported per item.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum BatchItem {
+    Ok { issues: Vec<GrammarIssue> },
+    Err { error: String },
+}
+
+/// Read `texts` from call arguments. `Ok(None)` when absent; an error when
+/// there are more than `max` of them.
+pub fn texts_from_args(
+    args: &serde_json::Value,
+    max: usize,
+) -> Result<Option<Vec<String>>, String> {
+    match args.get("texts") {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::Array(items)) if items.len() > max => Err(format!(
+            "'texts' has {} items; at most {max} are allowed",
+            items.len()
+        )),
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .map(|v| {
+                v.as_str()
+                    .map(String::from)
+                    .ok_or_else(|| "'texts' must be an array of strings".to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some),
+        Some(_) => Err("'texts' must be an array of strings".to_string()),
+    }
+}
+
+/// Check every text with at most `concurrency` upstream calls in flight.
+/// Results are returned in input order.
+pub async fn analyze_batch(
+    client: &GramadoirRemote,
+    texts: Vec<String>,
+    concurrency: usize,
+    filter: &RuleFilter,
+) -> Vec<BatchItem> {
+    let limit = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut results: Vec<Option<BatchItem>> = texts.iter().map(|_| None).collect();
+    let mut tasks = JoinSet::new();
+    for (index, text) in texts.into_iter().enumerate() {
+        let permit = limit
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("batch semaphore is never closed");
+        let client = client.clone();
+        tasks.spawn(telemetry::propagate(async move {
+            let _permit = permit;
+            (index, client.analyze(&text).await)
+        }));
+    }
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((index, Ok(issues))) => {
+                results[index] = Some(BatchItem::Ok {
+                    issues: filter.apply(issues),
+                })
+            }
+            Ok((index, Err(e))) => {
+                results[index] = Some(BatchItem::Err {
+                    error: e.to_string(),
+                })
+            }
+            Err(e) => tracing::warn!(error = %e, "grammar batch task failed"),
+        }
+    }
+    results
+        .into_iter()
+        .map(|r| {
+            r.unwrap_or_else(|| BatchItem::Err {
+                error: "grammar check task failed".to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    #[test]
+    fn texts_from_args_validates_shape() {
+        assert_eq!(texts_from_args(&json!({}), 8).unwrap(), None);
+        assert_eq!(
+            texts_from_args(&json!({"texts":["a","b"]}), 8).unwrap(),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert!(texts_from_args(&json!({"texts":"a"}), 8).is_err());
+        assert!(texts_from_args(&json!({"texts":["a",1]}), 8).is_err());
+        let err = texts_from_args(&json!({"texts":["a","b","c"]}), 2).unwrap_err();
+        assert!(err.contains("at most 2"));
+    }
+
+    #[tokio::test]
+    async fn it_preserves_order_and_reports_item_errors() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/api/gramadoir/1.0")
+                .json_body(json!({"teacs":"bad"}));
+            then.status(400).body("nope");
+        });
+        server.mock(|when, then| {
+            when.method(POST).path("/api/gramadoir/1.0");
+            then.status(200).json_body(json!([
+                {"msg":"x","ruleId":"SPELL","fromx":"0","errorlength":"1"}
+            ]));
+        });
+
+        let client = GramadoirRemote::new(server.base_url());
+        let texts = vec!["a".to_string(), "bad".to_string(), "c".to_string()];
+        let out = analyze_batch(&client, texts, 2, &RuleFilter::default()).await;
+        let out = serde_json::to_value(out).unwrap();
+        assert_eq!(out[0]["issues"][0]["code"], "SPELL");
+        assert!(out[1]["error"].as_str().unwrap().contains("400"));
+        assert_eq!(out[2]["issues"][0]["code"], "SPELL");
+    }
+
+    #[tokio::test]
+    async fn it_handles_empty_batches() {
+        let client = GramadoirRemote::new("http://localhost:0");
+        let out = analyze_batch(&client, Vec::new(), 0, &RuleFilter::default()).await;
+        assert!(out.is_empty());
+    }
+}
@@ -0,0 +1,132 @@
+//! Rule filtering for grammar results.
+
+use crate::domain::GrammarIssue;
+
+/// Which Gramadóir rule ids to keep.
+///
+/// A rule matches an issue code exactly, or by its last path segment with any
+/// `{...}` qualifier removed, so `CLAOCHLU` matches
+/// `Lingua::GA::Gramadoir/CLAOCHLU` and `CAIGHDEAN` matches `.../CAIGHDEAN{cúig}`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuleFilter {
+    /// When non-empty, only these rules are reported.
+    pub enable: Vec<String>,
+    pub disable: Vec<String>,
+}
+
+impl RuleFilter {
+    /// Build from `enable_rules`/`disable_rules` call arguments on top of the server's
+    /// default deny list. Explicitly enabled rules override the defaults.
+    pub fn from_args(
+        args: &serde_json::Value,
+        default_disabled: &[String],
+    ) -> Result<Self, String> {
+        let enable = string_list(args, "enable_rules")?;
+        let mut disable: Vec<String> = default_disabled
+            .iter()
+            .filter(|rule| !enable.iter().any(|e| e == *rule))
+            .cloned()
+            .collect();
+        disable.extend(string_list(args, "disable_rules")?);
+        Ok(Self { enable, disable })
+    }
+
+    pub fn allows(&self, code: &str) -> bool {
+        let matches = |rule: &String| rule_matches(rule, code);
+        (self.enable.is_empty() || self.enable.iter().any(matches))
+            && !self.disable.iter().any(matches)
+    }
+
+    pub fn apply(&self, issues: Vec<GrammarIssue>) -> Vec<GrammarIssue> {
+        issues
+            .into_iter()
+            .filter(|i| self.allows(&i.code))
+            .collect()
+    }
+}
+
+/// Whether `rule` names `code`, either exactly or by its short id
+/// (`CLAOCHLU` for `Lingua::GA::Gramadoir/CLAOCHLU{...}`).
+pub(crate) fn rule_matches(rule: &str, code: &str) -> bool {
+    if rule == code {
+        return true;
+    }
+    let last = code.rsplit('/').next().unwrap_or(code);
+    let bare = last.split('{').next().unwrap_or(last);
+    rule == last || rule == bare
+}
+
+pub(crate) fn string_list(args: &serde_json::Value, key: &str) -> Result<Vec<String>, String> {
+    match args.get(key) {
+        None | Some(serde_json::Value::Null) => Ok(Vec::new()),
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .map(|v| {
+                v.as_str()
+                    .map(String::from)
+                    .ok_or_else(|| format!("'{key}' must be an array of strings"))
+            })
+            .collect(),
+        Some(_) => Err(format!("'{key}' must be an array of strings")),
+    }
+}
+
+/// JSON schema properties shared by every grammar-check entry point.
+pub fn schema_properties() -> serde_json::Value {
+    serde_json::json!({
+        "enable_rules": {
+            "type": "array",
+            "items": { "type": "string" },
+            "description": "Only report issues from these rule ids"
+        },
+        "disable_rules": {
+            "type": "array",
+            "items": { "type": "string" },
+            "description": "Suppress issues from these rule ids"
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn matches_full_and_short_rule_ids() {
+        assert!(rule_matches(
+            "Lingua::GA::Gramadoir/CLAOCHLU",
+            "Lingua::GA::Gramadoir/CLAOCHLU"
+        ));
+        assert!(rule_matches("CLAOCHLU", "Lingua::GA::Gramadoir/CLAOCHLU"));
+        assert!(rule_matches(
+            "CAIGHDEAN",
+            "Lingua::GA::Gramadoir/CAIGHDEAN{cúig}"
+        ));
+        assert!(!rule_matches("CLAOCH", "Lingua::GA::Gramadoir/CLAOCHLU"));
+    }
+
+    #[test]
+    fn combines_defaults_with_call_arguments() {
+        let defaults = vec!["IASACHT".to_string(), "BACHOIR".to_string()];
+        let f = RuleFilter::from_args(&json!({"disable_rules":["CLAOCHLU"]}), &defaults).unwrap();
+        assert!(!f.allows("Lingua::GA::Gramadoir/IASACHT"));
+        assert!(!f.allows("CLAOCHLU"));
+        assert!(f.allows("SEIMHIU"));
+
+        let f = RuleFilter::from_args(&json!({"enable_rules":["IASACHT"]}), &defaults).unwrap();
+        assert!(f.allows("IASACHT"));
+        assert!(!f.allows("SEIMHIU"));
+        assert!(!f.allows("BACHOIR"));
+    }
+
+    #[test]
+    fn rejects_non_string_rule_lists() {
+        assert!(RuleFilter::from_args(&json!({"enable_rules":"X"}), &[]).is_err());
+        assert!(RuleFilter::from_args(&json!({"disable_rules":[1]}), &[]).is_err());
+        assert_eq!(
+            RuleFilter::from_args(&json!({"disable_rules":null}), &[]).unwrap(),
+            RuleFilter::default()
+        );
+    }
+}
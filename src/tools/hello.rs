@@ -1,3 +1,4 @@
+use crate::core::error::GatewayError;
 use crate::core::tool::{Tool, ToolSpec};
 use async_trait::async_trait;
 use serde_json::json;
@@ -16,11 +17,18 @@ impl ToolSpec for HelloTool {
     fn input_schema(&self) -> serde_json::Value {
         json!({ "type":"object", "properties": { "name": { "type":"string" } }, "required": [] })
     }
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        Some(json!({
+            "type": "object",
+            "properties": { "message": { "type": "string" } },
+            "required": ["message"]
+        }))
+    }
 }
 
 #[async_trait]
 impl Tool for HelloTool {
-    async fn call(&self, arguments: &serde_json::Value) -> Result<serde_json::Value, String> {
+    async fn call(&self, arguments: &serde_json::Value) -> Result<serde_json::Value, GatewayError> {
         let name = arguments
             .get("name")
             .and_then(|v| v.as_str())
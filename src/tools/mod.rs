@@ -1,3 +1,5 @@
+pub mod apply_fixes;
+pub mod cached;
 pub mod grammar;
 pub mod hello;
 pub mod registry;
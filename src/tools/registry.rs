@@ -1,39 +1,146 @@
+use crate::clients::gramadoir::GramadoirRemote;
 use crate::core::tool::Tool;
+use crate::infra::config::Config;
+use crate::infra::runtime::cache::ResultCache;
+use crate::tools::apply_fixes::ApplyFixesTool;
+use crate::tools::cached::CachedTool;
+use crate::tools::grammar::GrammarTool;
+use crate::tools::hello::HelloTool;
 use crate::tools::spellcheck::{SpellcheckLocalBackend, SpellcheckRemoteBackend};
 use std::{collections::HashMap, sync::Arc};
 
+pub mod live;
+pub mod tool_router;
+
+/// Every tool the gateway can expose, whether or not its upstream is configured.
+pub const TOOL_NAMES: [&str; 4] = [
+    "hello.echo",
+    "gael.spellcheck.v1",
+    "gael.grammar_check",
+    "gael.apply_fixes",
+];
+
 #[derive(Clone)]
 pub struct Registry(pub Arc<HashMap<&'static str, Arc<dyn Tool>>>);
 
-pub fn build_registry() -> Registry {
+impl Registry {
+    /// `name` if it is registered, else `"unknown"`, so callers cannot mint
+    /// new metric labels.
+    pub fn tool_label(&self, name: &str) -> &'static str {
+        self.0
+            .get_key_value(name)
+            .map_or("unknown", |(name, _)| *name)
+    }
+}
+
+/// Build the tools `cfg` enables. Grammar and spellcheck results go through
+/// `cache` when one is given.
+pub fn build_registry(cfg: &Config, cache: Option<Arc<ResultCache>>) -> Registry {
     let mut map: HashMap<&'static str, Arc<dyn Tool>> = HashMap::new();
 
-    // Always include spellcheck placeholder (local)
-    let spellcheck: Arc<dyn Tool> = Arc::new(SpellcheckLocalBackend);
+    let hello: Arc<dyn Tool> = Arc::new(HelloTool);
+    map.insert("hello.echo", hello);
+
+    // Always include local spellcheck (Hunspell dictionary from the configured path)
+    let spellcheck: Arc<dyn Tool> = Arc::new(SpellcheckLocalBackend::from_dict_path(
+        cfg.spellcheck_dict_path.as_deref(),
+    ));
     map.insert("gael.spellcheck.v1", spellcheck);
 
     // Conditionally include remote spellcheck if configured
-    if let Ok(base) = std::env::var("SPELLCHECK_BASE_URL") {
-        if !base.trim().is_empty() {
-            let remote_spellcheck: Arc<dyn Tool> = Arc::new(SpellcheckRemoteBackend::new(base));
-            map.insert("gael.spellcheck.v1", remote_spellcheck);
-        }
+    if let Some(base) = &cfg.spellcheck.base_url {
+        let remote_spellcheck: Arc<dyn Tool> =
+            Arc::new(SpellcheckRemoteBackend::new(base).with_upstream(&cfg.spellcheck));
+        map.insert("gael.spellcheck.v1", remote_spellcheck);
     }
 
+    // Serve repeated spellchecks of identical text from the result cache
+    if let (Some(cache), Some(spellcheck)) = (&cache, map.get("gael.spellcheck.v1").cloned()) {
+        let backend = match &cfg.spellcheck.base_url {
+            Some(base) => format!("remote:{base}"),
+            None => format!(
+                "local:{}",
+                cfg.spellcheck_dict_path.as_deref().unwrap_or_default()
+            ),
+        };
+        let cached: Arc<dyn Tool> = Arc::new(CachedTool::new(spellcheck, cache.clone(), backend));
+        map.insert("gael.spellcheck.v1", cached);
+    }
+
+    // Include grammar check and one-shot fixes when Gramadóir is configured
+    if let Some(base) = &cfg.gramadoir.base_url {
+        let grammar: Arc<dyn Tool> = Arc::new(
+            GrammarTool::new(base)
+                .with_upstream(&cfg.gramadoir)
+                .with_disabled_rules(cfg.grammar_disabled_rules.clone())
+                .with_batch_concurrency(cfg.grammar_batch_concurrency)
+                .with_batch_max_texts(cfg.grammar_batch_max_texts)
+                .with_cache(cache.clone()),
+        );
+        map.insert("gael.grammar_check", grammar);
+
+        let spellcheck = map.get("gael.spellcheck.v1").cloned();
+        let fixes: Arc<dyn Tool> = Arc::new(
+            ApplyFixesTool::new(
+                GramadoirRemote::new(base)
+                    .with_upstream(&cfg.gramadoir)
+                    .with_cache(cache),
+                spellcheck,
+            )
+            .with_disabled_rules(cfg.grammar_disabled_rules.clone()),
+        );
+        map.insert("gael.apply_fixes", fixes);
+    }
+
+    // Apply the tools allow/deny lists last so dependent tools keep their backends
+    map.retain(|name, _| cfg.tool_enabled(name));
+
     Registry(Arc::new(map))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serial_test::serial;
+
+    fn with_base_urls(gramadoir: Option<&str>, spellcheck: Option<&str>) -> Config {
+        let mut cfg = Config::default();
+        cfg.gramadoir.base_url = gramadoir.map(String::from);
+        cfg.spellcheck.base_url = spellcheck.map(String::from);
+        cfg
+    }
 
     #[test]
-    #[serial]
     fn it_includes_spellcheck_when_configured() {
-        std::env::set_var("SPELLCHECK_BASE_URL", "http://example");
-        let reg = build_registry();
+        let reg = build_registry(&with_base_urls(None, Some("http://example")), None);
         assert!(reg.0.contains_key("gael.spellcheck.v1"));
-        std::env::remove_var("SPELLCHECK_BASE_URL");
+    }
+
+    #[test]
+    fn it_includes_grammar_only_when_configured() {
+        let reg = build_registry(&Config::default(), None);
+        assert!(!reg.0.contains_key("gael.grammar_check"));
+        assert!(!reg.0.contains_key("gael.apply_fixes"));
+        assert!(reg.0.contains_key("hello.echo"));
+
+        let reg = build_registry(&with_base_urls(Some("http://example"), None), None);
+        assert!(reg.0.contains_key("gael.grammar_check"));
+        assert!(reg.0.contains_key("gael.apply_fixes"));
+    }
+
+    #[test]
+    fn it_applies_tool_allow_and_deny_lists() {
+        let mut cfg = with_base_urls(Some("http://example"), None);
+        cfg.tools_allow = Some(vec!["gael.apply_fixes".into(), "hello.echo".into()]);
+        cfg.tools_deny = vec!["hello.echo".into()];
+        let reg = build_registry(&cfg, None);
+        let names: Vec<_> = reg.0.keys().copied().collect();
+        assert_eq!(names, vec!["gael.apply_fixes"]);
+    }
+
+    #[test]
+    fn tool_names_cover_a_fully_configured_registry() {
+        let reg = build_registry(&with_base_urls(Some("http://example"), None), None);
+        assert_eq!(reg.0.len(), TOOL_NAMES.len());
+        assert!(TOOL_NAMES.iter().all(|name| reg.0.contains_key(name)));
     }
 }
@@ -0,0 +1,184 @@
+//! The registry currently being served, swappable at runtime.
+//!
+//! A config reload builds a fresh [`Registry`] and swaps it in atomically;
+//! MCP sessions resolve tools against whatever is current at request time, and
+//! every connected session is told via `notifications/tools/list_changed`
+//! when the set of tool names changed.
+
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex, RwLock};
+
+use axum::extract::FromRef;
+use rmcp::{Peer, RoleServer};
+
+use crate::infra::config::Config;
+use crate::infra::runtime::cache::ResultCache;
+use crate::tools::registry::{build_registry, Registry};
+
+#[derive(Clone)]
+pub struct LiveRegistry {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    current: RwLock<(Arc<Config>, Registry)>,
+    peers: Mutex<Vec<Peer<RoleServer>>>,
+    /// Created once from the initial config and kept across reloads.
+    cache: Option<Arc<ResultCache>>,
+}
+
+impl LiveRegistry {
+    pub fn new(cfg: Config) -> Self {
+        let cache = ResultCache::from_config(&cfg);
+        let registry = build_registry(&cfg, cache.clone());
+        Self {
+            inner: Arc::new(Inner {
+                current: RwLock::new((Arc::new(cfg), registry)),
+                peers: Mutex::new(Vec::new()),
+                cache,
+            }),
+        }
+    }
+
+    /// Config the current registry was built from.
+    pub fn config(&self) -> Arc<Config> {
+        self.inner.current.read().expect("registry lock").0.clone()
+    }
+
+    pub fn registry(&self) -> Registry {
+        self.inner.current.read().expect("registry lock").1.clone()
+    }
+
+    /// Result cache shared by every registry this serves, if caching is on.
+    pub fn cache(&self) -> Option<Arc<ResultCache>> {
+        self.inner.cache.clone()
+    }
+
+    /// Track an initialized MCP session so it hears about tool list changes.
+    pub fn subscribe(&self, peer: Peer<RoleServer>) {
+        let mut peers = self.inner.peers.lock().expect("peers lock");
+        peers.retain(|p| !p.is_transport_closed());
+        peers.push(peer);
+    }
+
+    /// Rebuild the registry from `cfg` and swap it in. Returns whether the set
+    /// of tool names changed, in which case every live session is notified.
+    /// The build loads dictionaries, so it runs on the blocking pool.
+    pub async fn reload(&self, cfg: Config) -> bool {
+        let cache = self.cache();
+        let (cfg, registry) = tokio::task::spawn_blocking(move || {
+            let registry = build_registry(&cfg, cache);
+            (cfg, registry)
+        })
+        .await
+        .expect("registry build panicked");
+        let changed = {
+            let mut current = self.inner.current.write().expect("registry lock");
+            let changed = tool_names(&current.1) != tool_names(&registry);
+            *current = (Arc::new(cfg), registry);
+            changed
+        };
+        if changed {
+            self.notify_tool_list_changed().await;
+        }
+        changed
+    }
+
+    async fn notify_tool_list_changed(&self) {
+        let peers: Vec<_> = {
+            let mut peers = self.inner.peers.lock().expect("peers lock");
+            peers.retain(|p| !p.is_transport_closed());
+            peers.clone()
+        };
+        for peer in peers {
+            if let Err(e) = peer.notify_tool_list_changed().await {
+                tracing::debug!(error = %e, "failed to notify session of tool list change");
+            }
+        }
+    }
+}
+
+/// Lets handlers keep extracting `State<Registry>` from a live app state.
+impl FromRef<LiveRegistry> for Registry {
+    fn from_ref(live: &LiveRegistry) -> Self {
+        live.registry()
+    }
+}
+
+fn tool_names(registry: &Registry) -> BTreeSet<&'static str> {
+    registry.0.keys().copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reload_swaps_registry_and_reports_name_changes() {
+        let live = LiveRegistry::new(Config::default());
+        assert!(live.registry().0.contains_key("hello.echo"));
+
+        let deny_hello = Config {
+            tools_deny: vec!["hello.echo".into()],
+            ..Config::default()
+        };
+        assert!(live.reload(deny_hello.clone()).await);
+        assert!(!live.registry().0.contains_key("hello.echo"));
+        assert_eq!(live.config().tools_deny, vec!["hello.echo".to_string()]);
+
+        // Same tool names, so no list_changed notification
+        assert!(!live.reload(deny_hello).await);
+    }
+
+    #[tokio::test]
+    async fn reload_notifies_initialized_sessions() {
+        use crate::infra::runtime::mcp_transport::serve_handler_with_io;
+        use crate::tools::registry::tool_router::RegistrySvc;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::time::{timeout, Duration};
+
+        let live = LiveRegistry::new(Config::default());
+        let (mut client, server) = tokio::io::duplex(16 * 1024);
+        let (srv_r, srv_w) = tokio::io::split(server);
+        tokio::spawn(serve_handler_with_io(
+            RegistrySvc::new(live.clone()),
+            srv_r,
+            srv_w,
+        ));
+
+        let init = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26","capabilities":{},"clientInfo":{"name":"test","version":"0.0.0"}}}"#;
+        let inited = r#"{"jsonrpc":"2.0","method":"notifications/initialized","params":{}}"#;
+        client
+            .write_all(format!("{init}\n{inited}\n").as_bytes())
+            .await
+            .unwrap();
+
+        // Wait for the session to subscribe, then swap in a smaller tool set
+        while live.inner.peers.lock().unwrap().is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let deny = Config {
+            tools_deny: vec!["hello.echo".into()],
+            ..Config::default()
+        };
+        assert!(live.reload(deny).await);
+
+        let mut out = String::new();
+        let mut buf = [0u8; 4096];
+        while !out.contains("notifications/tools/list_changed") {
+            let n = timeout(Duration::from_secs(2), client.read(&mut buf))
+                .await
+                .expect("timed out waiting for list_changed")
+                .unwrap();
+            assert!(n > 0, "server closed the stream");
+            out.push_str(&String::from_utf8_lossy(&buf[..n]));
+        }
+    }
+
+    #[test]
+    fn registry_state_is_extracted_from_live() {
+        let live = LiveRegistry::new(Config::default());
+        let reg = Registry::from_ref(&live);
+        assert_eq!(reg.0.len(), live.registry().0.len());
+    }
+}
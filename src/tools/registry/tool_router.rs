@@ -0,0 +1,282 @@
+//! Adapter exposing every tool in a [`LiveRegistry`] over MCP.
+//!
+//! rmcp's `Router` snapshots a `ToolRouter` per session, which would pin each
+//! session to the tools present when it connected. The handler instead lists
+//! and resolves tools against the live registry on every request, so a reload
+//! takes effect immediately for sessions that are already open.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use rmcp::model::{
+    CallToolRequestParam, CallToolResult, InitializeRequestParam, InitializeResult,
+    ListToolsResult, PaginatedRequestParam, ServerCapabilities, ServerInfo,
+};
+use rmcp::service::{NotificationContext, RequestContext};
+use rmcp::RoleServer;
+use tracing::Instrument;
+
+use crate::core::error::GatewayError;
+use crate::core::tool::Tool;
+use crate::infra::http::auth::{self, Principal};
+use crate::infra::http::headers::generate_request_id;
+use crate::infra::http::trace::Correlation;
+use crate::infra::metrics::{self, metrics};
+use crate::infra::runtime::mcp_transport::ServerHandler;
+use crate::infra::telemetry;
+use crate::tools::registry::live::LiveRegistry;
+
+#[derive(Clone)]
+pub struct RegistrySvc {
+    pub live: LiveRegistry,
+}
+
+impl RegistrySvc {
+    pub fn new(live: LiveRegistry) -> Self {
+        Self { live }
+    }
+
+    /// Tool metadata for everything currently registered that `principal` may
+    /// use, sorted by name.
+    pub fn tools(&self, principal: Option<&Principal>) -> Vec<rmcp::model::Tool> {
+        let registry = self.live.registry();
+        let mut tools: Vec<_> = registry
+            .0
+            .values()
+            .filter(|t| auth::permits(principal, t.name()))
+            .map(|t| tool_attr(t.as_ref()))
+            .collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        tools
+    }
+
+    /// Resolve `name` against the current registry and run it, provided
+    /// `principal` may use it.
+    pub async fn call(
+        &self,
+        name: &str,
+        arguments: Option<rmcp::model::JsonObject>,
+        principal: Option<&Principal>,
+    ) -> Result<CallToolResult, GatewayError> {
+        if let Some(p) = principal.filter(|p| !p.allows(name)) {
+            tracing::warn!(caller = %p.name, tool = %name, "tool call outside caller's scopes");
+            return Err(GatewayError::Forbidden(format!(
+                "'{}' may not call {name}",
+                p.name
+            )));
+        }
+        let tool = self
+            .live
+            .registry()
+            .0
+            .get(name)
+            .cloned()
+            .ok_or_else(|| GatewayError::NotFound(format!("tool not found: {name}")))?;
+        let args = serde_json::Value::Object(arguments.unwrap_or_default());
+        let out = tool
+            .call(&args)
+            .instrument(tracing::info_span!("tool.call", tool = name))
+            .await?;
+        Ok(CallToolResult::structured(out))
+    }
+}
+
+impl ServerHandler for RegistrySvc {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .build(),
+            ..ServerInfo::default()
+        }
+    }
+
+    async fn initialize(
+        &self,
+        request: InitializeRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<InitializeResult, rmcp::ErrorData> {
+        let started = Instant::now();
+        let (span, _) = request_span("initialize", &context);
+        let _entered = span.enter();
+        if context.peer.peer_info().is_none() {
+            context.peer.set_peer_info(request);
+        }
+        metrics().observe_request("initialize", "", "ok", started.elapsed());
+        Ok(self.get_info())
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, rmcp::ErrorData> {
+        let started = Instant::now();
+        // The HTTP transport hands the request parts through; the auth layer
+        // put the caller's principal in them.
+        let parts = context.extensions.get::<axum::http::request::Parts>();
+        let principal = parts.and_then(|p| p.extensions.get::<Principal>());
+        let (span, _) = request_span("tools/list", &context);
+        let tools = span.in_scope(|| self.tools(principal));
+        metrics().observe_request("tools/list", "", "ok", started.elapsed());
+        Ok(ListToolsResult {
+            next_cursor: None,
+            tools,
+        })
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let started = Instant::now();
+        let parts = context.extensions.get::<axum::http::request::Parts>();
+        let principal = parts.and_then(|p| p.extensions.get::<Principal>());
+        let (span, request_id) = request_span("tools/call", &context);
+        let result = telemetry::with_request_id(
+            request_id,
+            self.call(&request.name, request.arguments, principal),
+        )
+        .instrument(span)
+        .await;
+        metrics().observe_request(
+            "tools/call",
+            self.live.registry().tool_label(&request.name),
+            metrics::outcome(&result),
+            started.elapsed(),
+        );
+        Ok(result?)
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        self.live.subscribe(context.peer);
+    }
+}
+
+/// Span for one MCP request and its request id. Over HTTP both come from the
+/// request's [`Correlation`], so the span joins the HTTP request's trace.
+fn request_span(
+    method: &'static str,
+    context: &RequestContext<RoleServer>,
+) -> (tracing::Span, String) {
+    let parts = context.extensions.get::<axum::http::request::Parts>();
+    match parts.and_then(|p| p.extensions.get::<Correlation>()) {
+        Some(c) => (
+            tracing::info_span!(parent: &c.span, "mcp.request", rpc.method = method, request_id = %c.request_id),
+            c.request_id.clone(),
+        ),
+        None => {
+            let id = generate_request_id();
+            (
+                tracing::info_span!("mcp.request", rpc.method = method, request_id = %id),
+                id,
+            )
+        }
+    }
+}
+
+/// Handler factory for the MCP transports, serving whatever `live` currently holds.
+pub fn registry_factory(live: LiveRegistry) -> impl Fn() -> RegistrySvc + Send + Sync + Clone {
+    move || RegistrySvc::new(live.clone())
+}
+
+fn tool_attr(tool: &dyn Tool) -> rmcp::model::Tool {
+    let mut attr = rmcp::model::Tool::new(
+        tool.name(),
+        tool.description(),
+        Arc::new(rmcp::model::object(tool.input_schema())),
+    );
+    attr.output_schema = tool
+        .output_schema()
+        .map(|s| Arc::new(rmcp::model::object(s)));
+    attr
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::config::Config;
+
+    #[test]
+    fn handler_lists_every_registered_tool() {
+        let svc = RegistrySvc::new(LiveRegistry::new(Config::default()));
+        let names: Vec<_> = svc.tools(None).into_iter().map(|t| t.name).collect();
+        assert_eq!(names.len(), svc.live.registry().0.len());
+        for name in svc.live.registry().0.keys() {
+            assert!(names.iter().any(|n| n == name));
+        }
+    }
+
+    #[tokio::test]
+    async fn handler_follows_reloads() {
+        let svc = RegistrySvc::new(LiveRegistry::new(Config::default()));
+        let args = serde_json::json!({"name": "Aoife"});
+        let out = svc
+            .call("hello.echo", args.as_object().cloned(), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            out.structured_content.unwrap()["message"],
+            "Dia dhuit, Aoife!"
+        );
+
+        svc.live
+            .reload(Config {
+                tools_deny: vec!["hello.echo".into()],
+                ..Config::default()
+            })
+            .await;
+        assert!(!svc.tools(None).iter().any(|t| t.name == "hello.echo"));
+        let err = svc.call("hello.echo", None, None).await.unwrap_err();
+        assert!(matches!(err, GatewayError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn handler_refuses_tools_outside_the_callers_scopes() {
+        let svc = RegistrySvc::new(LiveRegistry::new(Config::default()));
+        let ci = Principal::new("ci", Some(vec!["gael.spellcheck.v1".into()]));
+        let args = serde_json::json!({"name": "Aoife"});
+        let err = svc
+            .call("hello.echo", args.as_object().cloned(), Some(&ci))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GatewayError::Forbidden(_)));
+        // Unknown tools are refused before they are looked up.
+        let err = svc.call("no.such.tool", None, Some(&ci)).await.unwrap_err();
+        assert!(matches!(err, GatewayError::Forbidden(_)));
+
+        let admin = Principal::new("admin", None);
+        assert!(svc
+            .call("hello.echo", args.as_object().cloned(), Some(&admin))
+            .await
+            .is_ok());
+    }
+
+    #[test]
+    fn tool_attr_carries_spec_metadata() {
+        let reg = LiveRegistry::new(Config::default()).registry();
+        let hello = reg.0.get("hello.echo").unwrap();
+        let attr = tool_attr(hello.as_ref());
+        assert_eq!(attr.name, "hello.echo");
+        assert_eq!(attr.description.as_deref(), Some(hello.description()));
+        assert_eq!(attr.input_schema["type"], "object");
+        let out = attr.output_schema.expect("hello declares an output schema");
+        assert_eq!(out["properties"]["message"]["type"], "string");
+    }
+
+    #[test]
+    fn info_advertises_tool_list_changes() {
+        let svc = RegistrySvc::new(LiveRegistry::new(Config::default()));
+        let tools = svc.get_info().capabilities.tools.unwrap();
+        assert_eq!(tools.list_changed, Some(true));
+    }
+
+    #[test]
+    fn test_server_handler_trait_impl() {
+        let svc = RegistrySvc::new(LiveRegistry::new(Config::default()));
+        fn assert_server_handler<T: ServerHandler>(_handler: T) {}
+        assert_server_handler(svc);
+    }
+}
@@ -0,0 +1,632 @@
+//! Minimal Hunspell `.aff`/`.dic` reader used by the local spellcheck backend.
+//!
+//! Supports the subset the Irish (gaeilge) dictionary relies on: `SET`, `FLAG`,
+//! `AF` aliases, `TRY`, `REP`, `NEEDAFFIX`, `FORBIDDENWORD` and `PFX`/`SFX`
+//! rules with cross products. Compounding and twofold suffixes are not handled.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+type Flag = u64;
+
+/// Fallback suggestion alphabet when the `.aff` file has no `TRY` line.
+const DEFAULT_TRY: &str = "aihnrtesclgodmábuíféóúpACBTSMFDGRLPÉNOEÁÓHIÍUÚ";
+const MAX_SUGGESTIONS: usize = 5;
+/// Longer tokens get no suggestions: single-edit candidates grow with length
+/// and each runs a full affix check. Irish words this long are rare and are
+/// usually run-together words or pasted identifiers.
+const MAX_SUGGEST_CHARS: usize = 32;
+
+#[derive(Clone, Copy, PartialEq)]
+enum FlagMode {
+    Char,
+    Long,
+    Num,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum CondPart {
+    Any,
+    Char(char),
+    Set { chars: Vec<char>, negated: bool },
+}
+
+#[derive(Debug, Clone)]
+struct AffixRule {
+    flag: Flag,
+    strip: String,
+    add: String,
+    cond: Vec<CondPart>,
+    cross: bool,
+}
+
+/// How a suggestion was derived; used for ranking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SuggestionKind {
+    /// Only differs from the input by an added/removed fada (acute accent).
+    Fada,
+    /// Produced by a `REP` table substitution.
+    Replacement,
+    /// Single-character edit (insert, delete, substitute, transpose).
+    Edit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub word: String,
+    pub kind: SuggestionKind,
+    pub score: f64,
+}
+
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    words: HashMap<String, Box<[Flag]>>,
+    prefixes: HashMap<String, Vec<AffixRule>>,
+    suffixes: HashMap<String, Vec<AffixRule>>,
+    try_chars: Vec<char>,
+    rep: Vec<(String, String)>,
+    need_affix: Option<Flag>,
+    forbidden: Option<Flag>,
+}
+
+impl Dictionary {
+    /// Load `<base>.aff` and `<base>.dic`. `base` may be given with or without an extension.
+    pub fn load(base: impl AsRef<Path>) -> Result<Self, String> {
+        let base = base.as_ref();
+        let aff_path = base.with_extension("aff");
+        let dic_path = base.with_extension("dic");
+        let aff =
+            std::fs::read(&aff_path).map_err(|e| format!("reading {}: {e}", aff_path.display()))?;
+        let dic =
+            std::fs::read(&dic_path).map_err(|e| format!("reading {}: {e}", dic_path.display()))?;
+        let encoding = detect_encoding(&aff);
+        let aff = decode(&aff, &encoding)?;
+        let dic = decode(&dic, &encoding)?;
+        Self::parse(&aff, &dic)
+    }
+
+    /// Parse already-decoded `.aff` and `.dic` contents.
+    pub fn parse(aff: &str, dic: &str) -> Result<Self, String> {
+        let mut d = Dictionary::default();
+        let mut mode = FlagMode::Char;
+        let mut aliases: Vec<Box<[Flag]>> = Vec::new();
+
+        // First pass: global options needed to decode flags in the rest of the file.
+        for line in aff.lines() {
+            let mut parts = line.split_whitespace();
+            if parts.next() == Some("FLAG") {
+                mode = match parts.next() {
+                    Some("long") => FlagMode::Long,
+                    Some("num") => FlagMode::Num,
+                    _ => FlagMode::Char,
+                };
+            }
+        }
+
+        let mut lines = aff.lines();
+        while let Some(line) = lines.next() {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            match parts.as_slice() {
+                ["TRY", chars, ..] => d.try_chars = chars.chars().collect(),
+                ["REP", from, to, ..] => d.rep.push((unescape(from), unescape(to))),
+                ["AF", flags, ..] if flags.parse::<usize>().is_err() => {
+                    aliases.push(parse_flags(flags, mode).into());
+                }
+                ["NEEDAFFIX", f, ..] => d.need_affix = parse_flags(f, mode).first().copied(),
+                ["FORBIDDENWORD", f, ..] => d.forbidden = parse_flags(f, mode).first().copied(),
+                [kind @ ("PFX" | "SFX"), name, cross, count, ..]
+                    if count.parse::<usize>().is_ok() =>
+                {
+                    let flag = *parse_flags(name, mode)
+                        .first()
+                        .ok_or_else(|| format!("bad affix flag: {name}"))?;
+                    let cross = *cross == "Y";
+                    let count: usize = count.parse().unwrap_or(0);
+                    for _ in 0..count {
+                        let Some(rule_line) = lines.next() else { break };
+                        let rp: Vec<&str> = rule_line.split_whitespace().collect();
+                        if rp.len() < 4 || rp[0] != *kind || rp[1] != *name {
+                            return Err(format!("malformed {kind} rule: {rule_line}"));
+                        }
+                        let strip = if rp[2] == "0" { "" } else { rp[2] };
+                        let add = rp[3].split('/').next().unwrap_or("");
+                        let add = if add == "0" { "" } else { add };
+                        let cond = parse_condition(rp.get(4).copied().unwrap_or("."));
+                        let rule = AffixRule {
+                            flag,
+                            strip: strip.to_string(),
+                            add: add.to_string(),
+                            cond,
+                            cross,
+                        };
+                        let table = if *kind == "PFX" {
+                            &mut d.prefixes
+                        } else {
+                            &mut d.suffixes
+                        };
+                        table.entry(rule.add.clone()).or_default().push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut dic_lines = dic.lines();
+        // First line is the approximate word count.
+        dic_lines.next();
+        for line in dic_lines {
+            let entry = line.split(['\t', ' ']).next().unwrap_or("").trim();
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+            let (word, flags) = split_entry(entry);
+            let flags: Vec<Flag> = match flags {
+                None => Vec::new(),
+                Some(f) if !aliases.is_empty() => f
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| aliases.get(i.wrapping_sub(1)))
+                    .map(|a| a.to_vec())
+                    .unwrap_or_default(),
+                Some(f) => parse_flags(f, mode),
+            };
+            let slot = d.words.entry(word).or_default();
+            if !flags.is_empty() {
+                let mut merged = slot.to_vec();
+                merged.extend(flags);
+                merged.sort_unstable();
+                merged.dedup();
+                *slot = merged.into();
+            }
+        }
+
+        if d.try_chars.is_empty() {
+            d.try_chars = DEFAULT_TRY.chars().collect();
+        }
+        Ok(d)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Whether `word` is accepted, trying lower-cased forms for capitalised input.
+    pub fn check(&self, word: &str) -> bool {
+        if self.check_exact(word) {
+            return true;
+        }
+        let lower = word.to_lowercase();
+        if lower != word && self.check_exact(&lower) {
+            return true;
+        }
+        // All-caps words may still be dictionary title case (e.g. proper nouns).
+        if is_all_upper(word) {
+            let title = to_title(&lower);
+            return title != word && self.check_exact(&title);
+        }
+        false
+    }
+
+    fn check_exact(&self, word: &str) -> bool {
+        if let Some(flags) = self.words.get(word) {
+            let forbidden = self.forbidden.is_some_and(|f| flags.contains(&f));
+            let needs_affix = self.need_affix.is_some_and(|f| flags.contains(&f));
+            if !forbidden && !needs_affix {
+                return true;
+            }
+            if forbidden {
+                return false;
+            }
+        }
+        self.check_suffixed(word, None) || self.check_prefixed(word)
+    }
+
+    fn has_flag(&self, stem: &str, flag: Flag) -> bool {
+        self.words.get(stem).is_some_and(|flags| {
+            flags.contains(&flag) && !self.forbidden.is_some_and(|f| flags.contains(&f))
+        })
+    }
+
+    /// Strip a suffix from `word`. When `prefix_flag` is set, the stem must also carry it
+    /// and the suffix rule must allow cross products.
+    fn check_suffixed(&self, word: &str, prefix_flag: Option<Flag>) -> bool {
+        for (idx, _) in word
+            .char_indices()
+            .chain(std::iter::once((word.len(), ' ')))
+        {
+            let (head, add) = word.split_at(idx);
+            if head.is_empty() {
+                continue;
+            }
+            let Some(rules) = self.suffixes.get(add) else {
+                continue;
+            };
+            for rule in rules {
+                if prefix_flag.is_some() && !rule.cross {
+                    continue;
+                }
+                let stem = format!("{head}{}", rule.strip);
+                if !cond_matches_end(&rule.cond, &stem) || !self.has_flag(&stem, rule.flag) {
+                    continue;
+                }
+                if prefix_flag.is_none_or(|pf| self.has_flag(&stem, pf)) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn check_prefixed(&self, word: &str) -> bool {
+        for (idx, _) in word.char_indices().skip(1) {
+            let (add, tail) = word.split_at(idx);
+            let Some(rules) = self.prefixes.get(add) else {
+                continue;
+            };
+            for rule in rules {
+                let stem = format!("{}{tail}", rule.strip);
+                if !cond_matches_start(&rule.cond, &stem) {
+                    continue;
+                }
+                if self.has_flag(&stem, rule.flag) {
+                    return true;
+                }
+                if rule.cross && self.check_suffixed(&stem, Some(rule.flag)) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Ranked replacement candidates for a misspelled `word`; none for words
+    /// longer than `MAX_SUGGEST_CHARS`.
+    pub fn suggest(&self, word: &str) -> Vec<Suggestion> {
+        if word.chars().count() > MAX_SUGGEST_CHARS {
+            return Vec::new();
+        }
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut out: Vec<Suggestion> = Vec::new();
+        let capitalised = word.chars().next().is_some_and(char::is_uppercase);
+        let lower = word.to_lowercase();
+        let mut push = |cand: String, kind: SuggestionKind, out: &mut Vec<Suggestion>| {
+            if cand.is_empty() || cand == lower || !seen.insert(cand.clone()) {
+                return;
+            }
+            if self.check(&cand) {
+                let score = score(kind, &lower, &cand);
+                let word = if capitalised { to_title(&cand) } else { cand };
+                out.push(Suggestion { word, kind, score });
+            }
+        };
+
+        for cand in fada_variants(&lower) {
+            push(cand, SuggestionKind::Fada, &mut out);
+        }
+        for (from, to) in &self.rep {
+            for (idx, _) in lower.match_indices(from.as_str()) {
+                let cand = format!("{}{}{}", &lower[..idx], to, &lower[idx + from.len()..]);
+                push(cand, SuggestionKind::Replacement, &mut out);
+            }
+        }
+        for cand in edits1(&lower, &self.try_chars) {
+            push(cand, SuggestionKind::Edit, &mut out);
+        }
+
+        out.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.word.cmp(&b.word))
+        });
+        out.truncate(MAX_SUGGESTIONS);
+        out
+    }
+}
+
+fn score(kind: SuggestionKind, original: &str, cand: &str) -> f64 {
+    let base = match kind {
+        SuggestionKind::Fada => 0.95,
+        SuggestionKind::Replacement => 0.85,
+        SuggestionKind::Edit => 0.75,
+    };
+    let len_diff = original.chars().count().abs_diff(cand.chars().count()) as f64;
+    ((base - 0.05 * len_diff).max(0.0) * 100.0).round() / 100.0
+}
+
+fn toggle_fada(c: char) -> Option<char> {
+    Some(match c {
+        'a' => 'á',
+        'e' => 'é',
+        'i' => 'í',
+        'o' => 'ó',
+        'u' => 'ú',
+        'á' => 'a',
+        'é' => 'e',
+        'í' => 'i',
+        'ó' => 'o',
+        'ú' => 'u',
+        _ => return None,
+    })
+}
+
+fn fada_variants(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let mut out = Vec::new();
+    for (i, c) in chars.iter().enumerate() {
+        if let Some(t) = toggle_fada(*c) {
+            let mut v = chars.clone();
+            v[i] = t;
+            out.push(v.into_iter().collect());
+        }
+    }
+    out
+}
+
+fn edits1(word: &str, alphabet: &[char]) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let lower_alpha: Vec<char> = alphabet
+        .iter()
+        .copied()
+        .filter(|c| !c.is_uppercase())
+        .collect();
+    let mut out = Vec::new();
+    for i in 0..chars.len() {
+        let mut v = chars.clone();
+        v.remove(i);
+        out.push(v.into_iter().collect());
+    }
+    for i in 0..chars.len().saturating_sub(1) {
+        let mut v = chars.clone();
+        v.swap(i, i + 1);
+        out.push(v.into_iter().collect());
+    }
+    for i in 0..chars.len() {
+        for &c in &lower_alpha {
+            if chars[i] != c {
+                let mut v = chars.clone();
+                v[i] = c;
+                out.push(v.into_iter().collect());
+            }
+        }
+    }
+    for i in 0..=chars.len() {
+        for &c in &lower_alpha {
+            let mut v = chars.clone();
+            v.insert(i, c);
+            out.push(v.into_iter().collect());
+        }
+    }
+    out
+}
+
+fn is_all_upper(word: &str) -> bool {
+    word.chars().any(char::is_alphabetic) && !word.chars().any(char::is_lowercase)
+}
+
+fn to_title(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn unescape(s: &str) -> String {
+    s.replace('_', " ")
+}
+
+fn split_entry(entry: &str) -> (String, Option<&str>) {
+    let bytes = entry.as_bytes();
+    let mut idx = None;
+    for (i, b) in bytes.iter().enumerate() {
+        if *b == b'/' && (i == 0 || bytes[i - 1] != b'\\') {
+            idx = Some(i);
+            break;
+        }
+    }
+    match idx {
+        Some(i) if i > 0 => (entry[..i].replace("\\/", "/"), Some(&entry[i + 1..])),
+        _ => (entry.replace("\\/", "/"), None),
+    }
+}
+
+fn parse_flags(s: &str, mode: FlagMode) -> Vec<Flag> {
+    match mode {
+        FlagMode::Char => s.chars().map(|c| c as Flag).collect(),
+        FlagMode::Long => s
+            .chars()
+            .collect::<Vec<_>>()
+            .chunks(2)
+            .map(|pair| pair.iter().fold(0, |acc, c| (acc << 21) | *c as Flag))
+            .collect(),
+        FlagMode::Num => s
+            .split(',')
+            .filter_map(|n| n.trim().parse::<Flag>().ok())
+            .collect(),
+    }
+}
+
+fn parse_condition(cond: &str) -> Vec<CondPart> {
+    let mut out = Vec::new();
+    let mut chars = cond.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => out.push(CondPart::Any),
+            '[' => {
+                let mut set = Vec::new();
+                let mut negated = false;
+                for (i, sc) in chars.by_ref().enumerate() {
+                    match sc {
+                        ']' => break,
+                        '^' if i == 0 => negated = true,
+                        _ => set.push(sc),
+                    }
+                }
+                out.push(CondPart::Set {
+                    chars: set,
+                    negated,
+                });
+            }
+            _ => out.push(CondPart::Char(c)),
+        }
+    }
+    out
+}
+
+fn part_matches(part: &CondPart, c: char) -> bool {
+    match part {
+        CondPart::Any => true,
+        CondPart::Char(p) => *p == c,
+        CondPart::Set { chars, negated } => chars.contains(&c) != *negated,
+    }
+}
+
+fn cond_matches_end(cond: &[CondPart], stem: &str) -> bool {
+    let chars: Vec<char> = stem.chars().collect();
+    if cond.len() > chars.len() {
+        return false;
+    }
+    cond.iter()
+        .zip(&chars[chars.len() - cond.len()..])
+        .all(|(p, c)| part_matches(p, *c))
+}
+
+fn cond_matches_start(cond: &[CondPart], stem: &str) -> bool {
+    let chars: Vec<char> = stem.chars().collect();
+    if cond.len() > chars.len() {
+        return false;
+    }
+    cond.iter().zip(&chars).all(|(p, c)| part_matches(p, *c))
+}
+
+fn detect_encoding(aff: &[u8]) -> String {
+    String::from_utf8_lossy(aff)
+        .lines()
+        .find_map(|l| l.strip_prefix("SET "))
+        .map(|s| s.trim().to_ascii_uppercase())
+        .unwrap_or_else(|| "ISO8859-1".into())
+}
+
+fn decode(bytes: &[u8], encoding: &str) -> Result<String, String> {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    match encoding {
+        "UTF-8" => String::from_utf8(bytes.to_vec()).map_err(|e| e.to_string()),
+        "ISO8859-1" | "ISO-8859-1" | "LATIN1" => Ok(bytes.iter().map(|&b| b as char).collect()),
+        other => Err(format!("unsupported dictionary encoding: {other}")),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const AFF: &str = "SET UTF-8\n\
+TRY aeiouáéíóúbcdfghlmnprst\n\
+REP 1\n\
+REP bh v\n\
+PFX H Y 1\n\
+PFX H 0 h [aeiouáéíóú]\n\
+PFX L Y 2\n\
+PFX L b bh b\n\
+PFX L c ch c\n\
+SFX S Y 2\n\
+SFX S 0 anna [^e]\n\
+SFX S e í e\n";
+
+    pub(crate) const DIC: &str = "6\n\
+bord/LS\n\
+cóta/S\n\
+cailín/L\n\
+úll/HS\n\
+páiste/S\n\
+Éire\n";
+
+    fn dict() -> Dictionary {
+        Dictionary::parse(AFF, DIC).unwrap()
+    }
+
+    #[test]
+    fn accepts_stems_and_affixed_forms() {
+        let d = dict();
+        assert_eq!(d.len(), 6);
+        assert!(d.check("bord"));
+        assert!(d.check("bordanna"));
+        assert!(d.check("bhord"));
+        assert!(d.check("bhordanna"));
+        assert!(d.check("páistí"));
+        assert!(d.check("húll"));
+        assert!(d.check("Bord"));
+        assert!(d.check("ÉIRE"));
+        assert!(!d.check("bordí"));
+        assert!(!d.check("chóta"));
+    }
+
+    #[test]
+    fn suggests_missing_fada_first() {
+        let d = dict();
+        let s = d.suggest("cota");
+        assert_eq!(s[0].word, "cóta");
+        assert_eq!(s[0].kind, SuggestionKind::Fada);
+    }
+
+    #[test]
+    fn suggests_edits_and_preserves_case() {
+        let d = dict();
+        let s = d.suggest("Brod");
+        assert!(s.iter().any(|x| x.word == "Bord"));
+        let s = d.suggest("cailin");
+        assert_eq!(s[0].word, "cailín");
+    }
+
+    #[test]
+    fn skips_suggestions_for_overlong_tokens() {
+        let d = dict();
+        let long = "cota".repeat(MAX_SUGGEST_CHARS / 4 + 1);
+        assert!(!d.check(&long));
+        assert!(d.suggest(&long).is_empty());
+    }
+
+    #[test]
+    fn parses_long_and_numeric_flags() {
+        let aff = "FLAG long\nSFX Aa Y 1\nSFX Aa 0 í .\n";
+        let d = Dictionary::parse(aff, "1\nbád/Aa\n").unwrap();
+        assert!(d.check("bádí"));
+
+        let aff = "FLAG num\nSFX 12 Y 1\nSFX 12 0 í .\n";
+        let d = Dictionary::parse(aff, "1\nbád/7,12\n").unwrap();
+        assert!(d.check("bádí"));
+    }
+
+    #[test]
+    fn honours_flag_aliases_and_needaffix() {
+        let aff = "AF 1\nAF SN\nNEEDAFFIX N\nSFX S Y 1\nSFX S 0 a .\n";
+        let d = Dictionary::parse(aff, "1\nfocal/1\n").unwrap();
+        assert!(!d.check("focal"));
+        assert!(d.check("focala"));
+    }
+
+    #[test]
+    fn rejects_malformed_affix_block() {
+        let aff = "SFX S Y 1\nPFX S 0 a .\n";
+        assert!(Dictionary::parse(aff, "0\n").is_err());
+    }
+
+    #[test]
+    fn loads_latin1_files_from_disk() {
+        let dir = std::env::temp_dir().join(format!("hunspell-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let base = dir.join("ga_IE");
+        std::fs::write(base.with_extension("aff"), b"SET ISO8859-1\n").unwrap();
+        std::fs::write(base.with_extension("dic"), b"1\nc\xf3ta\n").unwrap();
+        let d = Dictionary::load(&base).unwrap();
+        assert!(d.check("cóta"));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(Dictionary::load(dir.join("missing")).is_err());
+    }
+}
@@ -1,41 +1,253 @@
+use std::path::Path;
+use std::sync::Arc;
+
 use async_trait::async_trait;
 
+use crate::core::error::GatewayError;
 use crate::core::tool::{Tool, ToolSpec};
+use crate::domain::{SpellCorrection, SpellReason, SpellSuggestion};
+use crate::tools::spellcheck::hunspell::Dictionary;
 
+/// Offline spellcheck backed by a Hunspell `.dic`/`.aff` pair.
+///
+/// Without a dictionary (the `Default`), every input is accepted.
 #[derive(Clone, Default)]
-pub struct SpellcheckLocalBackend;
+pub struct SpellcheckLocalBackend {
+    dict: Option<Arc<Dictionary>>,
+}
+
+impl SpellcheckLocalBackend {
+    pub fn with_dictionary(dict: Dictionary) -> Self {
+        Self {
+            dict: Some(Arc::new(dict)),
+        }
+    }
+
+    /// Load `<base>.aff`/`<base>.dic`, e.g. `/usr/share/hunspell/ga_IE`.
+    pub fn load(base: impl AsRef<Path>) -> Result<Self, String> {
+        Dictionary::load(base).map(Self::with_dictionary)
+    }
+
+    /// Build from the configured dictionary path, falling back to the empty backend
+    /// when unset or unreadable.
+    pub fn from_dict_path(path: Option<&str>) -> Self {
+        let Some(path) = path.filter(|p| !p.trim().is_empty()) else {
+            return Self::default();
+        };
+        match Self::load(path) {
+            Ok(backend) => {
+                let words = backend.dict.as_ref().map_or(0, |d| d.len());
+                tracing::info!(path = %path, words, "loaded spellcheck dictionary");
+                if backend.dict.as_ref().is_some_and(|d| d.is_empty()) {
+                    tracing::warn!(path = %path, "spellcheck dictionary has no words");
+                }
+                backend
+            }
+            Err(e) => {
+                tracing::warn!(path = %path, error = %e, "failed to load spellcheck dictionary");
+                Self::default()
+            }
+        }
+    }
+
+    fn check_text(&self, dict: &Dictionary, text: &str) -> Vec<SpellCorrection> {
+        tokenize(text)
+            .into_iter()
+            .filter(|t| !accepts(dict, t.text))
+            .map(|t| {
+                let suggestions: Vec<SpellSuggestion> = dict
+                    .suggest(t.text)
+                    .into_iter()
+                    .map(|s| SpellSuggestion {
+                        word: s.word,
+                        score: Some(s.score),
+                    })
+                    .collect();
+                SpellCorrection {
+                    token: t.text.to_string(),
+                    start: t.char_start,
+                    end: t.char_end,
+                    byte_start: t.byte_start,
+                    byte_end: t.byte_end,
+                    reason: SpellReason::classify(
+                        t.text,
+                        suggestions.first().map(|s| s.word.as_str()),
+                    ),
+                    suggestions,
+                }
+            })
+            .collect()
+    }
+}
 
 impl ToolSpec for SpellcheckLocalBackend {
     fn name(&self) -> &'static str {
         "gael.spellcheck.v1"
     }
     fn description(&self) -> &'static str {
-        "Irish spellcheck (local placeholder)"
+        "Irish spellcheck (local Hunspell dictionary)"
     }
     fn input_schema(&self) -> serde_json::Value {
         serde_json::json!({"type":"object","properties":{"text":{"type":"string"}},"required":["text"]})
     }
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        Some(SpellCorrection::result_schema())
+    }
 }
 
 #[async_trait]
 impl Tool for SpellcheckLocalBackend {
-    async fn call(&self, args: &serde_json::Value) -> Result<serde_json::Value, String> {
-        let _ = args
+    async fn call(&self, args: &serde_json::Value) -> Result<serde_json::Value, GatewayError> {
+        let text = args
             .get("text")
             .and_then(|v| v.as_str())
-            .ok_or("missing 'text'")?;
-        Ok(serde_json::json!({"corrections": []}))
+            .ok_or_else(|| GatewayError::InvalidParams("missing 'text'".into()))?;
+        let corrections = match &self.dict {
+            Some(dict) => self.check_text(dict, text),
+            None => Vec::new(),
+        };
+        Ok(serde_json::json!({ "corrections": corrections }))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Token<'a> {
+    text: &'a str,
+    byte_start: usize,
+    byte_end: usize,
+    char_start: usize,
+    char_end: usize,
+}
+
+fn is_joiner(c: char) -> bool {
+    matches!(c, '\'' | '’' | '-')
+}
+
+/// Split `text` into word tokens. Apostrophes and hyphens are kept when they sit
+/// between letters (`d'ól`, `t-uisce`); tokens containing digits are skipped.
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].1.is_alphanumeric() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < chars.len() {
+            let c = chars[i].1;
+            let joined = is_joiner(c)
+                && i > start
+                && chars.get(i + 1).is_some_and(|(_, n)| n.is_alphanumeric());
+            if c.is_alphanumeric() || joined {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+        let byte_start = chars[start].0;
+        let byte_end = chars.get(i).map_or(text.len(), |(b, _)| *b);
+        let word = &text[byte_start..byte_end];
+        if !word.chars().any(|c| c.is_numeric()) {
+            out.push(Token {
+                text: word,
+                byte_start,
+                byte_end,
+                char_start: start,
+                char_end: i,
+            });
+        }
     }
+    out
+}
+
+/// Dictionary check with allowances for Irish elision (`d'`, `m'`, `b'`) and
+/// hyphenated initial mutations (`t-`, `n-`, `h-`).
+fn accepts(dict: &Dictionary, word: &str) -> bool {
+    if dict.check(word) {
+        return true;
+    }
+    let lower = word.to_lowercase();
+    for elided in ["d'", "d’", "m'", "m’", "b'", "b’"] {
+        if let Some(rest) = lower.strip_prefix(elided) {
+            return dict.check(rest);
+        }
+    }
+    for mutation in ["t-", "n-", "h-"] {
+        if let Some(rest) = lower.strip_prefix(mutation) {
+            return dict.check(rest);
+        }
+    }
+    word.contains('-') && word.split('-').all(|part| dict.check(part))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tools::spellcheck::hunspell::tests::{AFF, DIC};
+
+    fn backend() -> SpellcheckLocalBackend {
+        SpellcheckLocalBackend::with_dictionary(Dictionary::parse(AFF, DIC).unwrap())
+    }
 
     #[tokio::test]
     async fn local_backend_placeholder_returns_empty() {
-        let tool = SpellcheckLocalBackend;
+        let tool = SpellcheckLocalBackend::default();
         let out = tool.call(&serde_json::json!({"text":"Dia"})).await.unwrap();
         assert!(out["corrections"].as_array().unwrap().is_empty());
     }
+
+    #[tokio::test]
+    async fn it_reports_unknown_words_with_offsets_and_suggestions() {
+        let tool = backend();
+        let out = tool
+            .call(&serde_json::json!({"text":"Éire: cota ar an bord"}))
+            .await
+            .unwrap();
+        let corrections = out["corrections"].as_array().unwrap();
+        // "ar" and "an" are not in the fixture dictionary either
+        assert_eq!(corrections.len(), 3);
+        assert_eq!(corrections[0]["token"], "cota");
+        assert_eq!(corrections[0]["start"], 6);
+        assert_eq!(corrections[0]["end"], 10);
+        // "É" is two bytes in UTF-8
+        assert_eq!(corrections[0]["byte_start"], 7);
+        assert_eq!(corrections[0]["byte_end"], 11);
+        assert_eq!(corrections[0]["reason"], "missing-fada");
+        assert_eq!(corrections[0]["suggestions"][0]["word"], "cóta");
+        assert_eq!(corrections[0]["suggestions"][0]["score"], 0.95);
+        assert_eq!(corrections[1]["reason"], "unknown-word");
+    }
+
+    #[tokio::test]
+    async fn it_validates_missing_text() {
+        let err = backend().call(&serde_json::json!({})).await.unwrap_err();
+        assert!(err.to_string().contains("missing 'text'"));
+    }
+
+    #[test]
+    fn tokenize_keeps_internal_joiners_and_skips_numbers() {
+        let toks: Vec<&str> = tokenize("d'ól an t-uisce - 2024 'sin'")
+            .into_iter()
+            .map(|t| t.text)
+            .collect();
+        assert_eq!(toks, vec!["d'ól", "an", "t-uisce", "sin"]);
+    }
+
+    #[test]
+    fn accepts_elision_and_hyphenated_mutations() {
+        let dict = Dictionary::parse(AFF, DIC).unwrap();
+        assert!(accepts(&dict, "d'úll"));
+        assert!(accepts(&dict, "t-úll"));
+        assert!(accepts(&dict, "bord-cóta"));
+        assert!(!accepts(&dict, "t-cota"));
+    }
+
+    #[test]
+    fn from_dict_path_falls_back_when_unset_or_unreadable() {
+        let b = SpellcheckLocalBackend::from_dict_path(Some("/definitely/not/here/ga_IE"));
+        assert!(b.dict.is_none());
+        assert!(SpellcheckLocalBackend::from_dict_path(None).dict.is_none());
+    }
 }
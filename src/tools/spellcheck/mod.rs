@@ -1,3 +1,4 @@
+pub mod hunspell;
 pub mod local;
 pub mod remote;
 pub use local::SpellcheckLocalBackend;
@@ -1,24 +1,70 @@
+//! Remote spellcheck backend.
+//!
+//! Wire format (`POST {base_url}/api/spellcheck/1.0`):
+//!
+//! ```text
+//! request:  {"text": "Tá an cota ar an bord"}
+//! response: {"corrections": [
+//!              {"token": "cota", "start": 6, "end": 10, "reason": "missing-fada",
+//!               "suggestions": ["cóta", {"word": "cata", "score": 0.4}]}
+//!           ]}
+//! ```
+//!
+//! `start`/`end` are Unicode scalar (char) offsets into `text`. Suggestions are
+//! ranked best first and may be plain strings or `{word, score}` objects. `reason`
+//! is optional (`unknown-word` | `missing-fada`) and derived locally when absent.
+
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
 
+use crate::core::error::GatewayError;
 use crate::core::tool::{Tool, ToolSpec};
-use crate::infra::http::headers::generate_request_id;
-use crate::infra::runtime::limits::make_http_client;
+use crate::domain::{SpellCorrection, SpellReason, SpellSuggestion};
+use crate::infra::config::UpstreamConfig;
+use crate::infra::http::headers::{add_standard_headers, generate_request_id};
+use crate::infra::metrics::{self, metrics};
+use crate::infra::runtime::breaker::CircuitBreaker;
+use crate::infra::runtime::limits::{
+    make_http_client, make_upstream_client, retry_if, AttemptError, RetryPolicy,
+};
+use crate::infra::runtime::queue::UpstreamQueue;
+use crate::infra::telemetry::current_request_id;
 
 #[derive(Clone)]
 pub struct SpellcheckRemoteBackend {
-    #[allow(dead_code)]
     pub(crate) base_url: String,
     http: reqwest::Client,
+    breaker: Arc<CircuitBreaker>,
+    queue: Arc<UpstreamQueue>,
+    retry: RetryPolicy,
 }
 
 impl SpellcheckRemoteBackend {
     pub fn new(base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into();
         Self {
-            base_url: base_url.into(),
+            breaker: CircuitBreaker::for_upstream(&base_url),
+            queue: UpstreamQueue::for_upstream(&base_url),
+            base_url,
             http: make_http_client(),
+            retry: UpstreamConfig::default().retry_policy(),
         }
     }
 
+    /// Apply timeout, retry, pool, HTTP/2, circuit breaker and queue settings.
+    pub fn with_upstream(mut self, cfg: &UpstreamConfig) -> Self {
+        self.http = make_upstream_client(cfg);
+        self.retry = cfg.retry_policy();
+        self.breaker
+            .configure(cfg.breaker_failure_threshold, cfg.breaker_open_for);
+        self.queue
+            .configure(cfg.max_concurrent, cfg.max_queue, cfg.queue_timeout);
+        self
+    }
+
     #[allow(dead_code)]
     pub async fn health(&self) -> bool {
         let id = generate_request_id();
@@ -28,6 +74,134 @@ impl SpellcheckRemoteBackend {
             Err(_) => false,
         }
     }
+
+    pub async fn check(&self, text: &str) -> Result<Vec<CorrectionWire>, GatewayError> {
+        let url = format!("{}/api/spellcheck/1.0", self.base_url.trim_end_matches('/'));
+        let http = self.http.clone();
+        let url_clone = url.clone();
+        tracing::debug!(endpoint = %url, "spellcheck.check request");
+        let req_id = current_request_id().unwrap_or_else(generate_request_id);
+        let span = tracing::info_span!(
+            "upstream.call",
+            otel.kind = "client",
+            upstream = "spellcheck",
+            url.full = %url,
+            request_id = %req_id,
+        );
+        let attempt = retry_if(self.retry, AttemptError::is_unavailable, move |_| {
+            let http = http.clone();
+            let url = url_clone.clone();
+            let req_id = req_id.clone();
+            let payload = SpellReq { text };
+            async move {
+                let (builder, _rid) = add_standard_headers(http.post(url), Some(req_id));
+                let resp = builder
+                    .json(&payload)
+                    .send()
+                    .await
+                    .map_err(AttemptError::from)?;
+                if !resp.status().is_success() {
+                    if resp.status().is_server_error() {
+                        return Err(AttemptError::Unavailable(format!(
+                            "retryable status {}",
+                            resp.status()
+                        )));
+                    }
+                    return Err(AttemptError::BadResponse(format!(
+                        "upstream status {}",
+                        resp.status()
+                    )));
+                }
+                resp.json::<SpellResp>().await.map_err(AttemptError::from)
+            }
+        });
+        // Queue only once the breaker admits the call, so callers fail fast
+        // while it is open. Retries keep their slot, so a struggling upstream
+        // never sees more than `max_concurrent` requests from us
+        let permit = self.breaker.acquire();
+        let _slot = match permit {
+            Ok(_) => Some(self.queue.acquire().await?),
+            Err(_) => None,
+        };
+        let started = std::time::Instant::now();
+        let result = async { permit?.run(attempt).await }.instrument(span).await;
+        metrics().observe_upstream("spellcheck", metrics::outcome(&result), started.elapsed());
+        let resp: SpellResp = result?;
+        Ok(resp.corrections)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SpellReq<'a> {
+    text: &'a str,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SpellResp {
+    #[serde(default)]
+    corrections: Vec<CorrectionWire>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CorrectionWire {
+    pub token: String,
+    pub start: usize,
+    pub end: usize,
+    #[serde(default)]
+    pub reason: Option<SpellReason>,
+    #[serde(default)]
+    pub suggestions: Vec<SuggestionWire>,
+}
+
+impl CorrectionWire {
+    /// Map into the domain model, deriving byte offsets from `text` and classifying
+    /// the reason locally when upstream does not provide one.
+    pub fn into_correction(self, text: &str) -> SpellCorrection {
+        let suggestions: Vec<SpellSuggestion> = self
+            .suggestions
+            .into_iter()
+            .map(SpellSuggestion::from)
+            .collect();
+        let reason = self.reason.unwrap_or_else(|| {
+            SpellReason::classify(&self.token, suggestions.first().map(|s| s.word.as_str()))
+        });
+        SpellCorrection {
+            byte_start: byte_offset(text, self.start),
+            byte_end: byte_offset(text, self.end),
+            token: self.token,
+            start: self.start,
+            end: self.end,
+            reason,
+            suggestions,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SuggestionWire {
+    Plain(String),
+    Scored {
+        word: String,
+        #[serde(default)]
+        score: Option<f64>,
+    },
+}
+
+impl From<SuggestionWire> for SpellSuggestion {
+    fn from(w: SuggestionWire) -> Self {
+        match w {
+            SuggestionWire::Plain(word) => SpellSuggestion { word, score: None },
+            SuggestionWire::Scored { word, score } => SpellSuggestion { word, score },
+        }
+    }
+}
+
+/// Byte offset of the `char_idx`-th char in `text` (clamped to the end).
+fn byte_offset(text: &str, char_idx: usize) -> usize {
+    text.char_indices()
+        .nth(char_idx)
+        .map_or(text.len(), |(b, _)| b)
 }
 
 impl ToolSpec for SpellcheckRemoteBackend {
@@ -35,22 +209,30 @@ impl ToolSpec for SpellcheckRemoteBackend {
         "gael.spellcheck.v1"
     }
     fn description(&self) -> &'static str {
-        "Irish spellcheck (remote backend placeholder)"
+        "Irish spellcheck (remote backend)"
     }
     fn input_schema(&self) -> serde_json::Value {
         serde_json::json!({"type":"object","properties":{"text":{"type":"string"}},"required":["text"]})
     }
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        Some(SpellCorrection::result_schema())
+    }
 }
 
 #[async_trait]
 impl Tool for SpellcheckRemoteBackend {
-    async fn call(&self, args: &serde_json::Value) -> Result<serde_json::Value, String> {
-        let _ = args
+    async fn call(&self, args: &serde_json::Value) -> Result<serde_json::Value, GatewayError> {
+        let text = args
             .get("text")
             .and_then(|v| v.as_str())
-            .ok_or("missing 'text'")?;
-        // Placeholder: just echo empty corrections for now
-        Ok(serde_json::json!({"corrections": []}))
+            .ok_or_else(|| GatewayError::InvalidParams("missing 'text'".into()))?;
+        let corrections: Vec<SpellCorrection> = self
+            .check(text)
+            .await?
+            .into_iter()
+            .map(|c| c.into_correction(text))
+            .collect();
+        Ok(serde_json::json!({ "corrections": corrections }))
     }
 
     async fn health(&self) -> bool {
@@ -62,19 +244,67 @@ impl Tool for SpellcheckRemoteBackend {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use httpmock::prelude::*;
+    use serde_json::json;
 
     #[tokio::test]
-    async fn remote_backend_returns_empty_on_happy_path() {
-        let tool = SpellcheckRemoteBackend::new("http://example");
-        let out = tool.call(&serde_json::json!({"text":"Dia"})).await.unwrap();
+    async fn remote_backend_maps_corrections_from_wire() {
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(POST)
+                .path("/api/spellcheck/1.0")
+                .header_exists("x-request-id")
+                .json_body(json!({"text":"Éire: cota"}));
+            then.status(200).json_body(json!({"corrections":[{
+                "token":"cota","start":6,"end":10,
+                "suggestions":["cóta", {"word":"cata","score":0.4}]
+            }]}));
+        });
+
+        let tool = SpellcheckRemoteBackend::new(server.base_url());
+        let out = tool.call(&json!({"text":"Éire: cota"})).await.unwrap();
+        m.assert();
+        let c = &out["corrections"][0];
+        assert_eq!(c["token"], "cota");
+        assert_eq!(c["start"], 6);
+        assert_eq!(c["byte_start"], 7);
+        assert_eq!(c["byte_end"], 11);
+        assert_eq!(c["reason"], "missing-fada");
+        assert_eq!(
+            c["suggestions"],
+            json!([{"word":"cóta","score":null}, {"word":"cata","score":0.4}])
+        );
+    }
+
+    #[tokio::test]
+    async fn remote_backend_returns_empty_when_no_corrections() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/api/spellcheck/1.0");
+            then.status(200).json_body(json!({}));
+        });
+        let tool = SpellcheckRemoteBackend::new(server.base_url());
+        let out = tool.call(&json!({"text":"Dia"})).await.unwrap();
         assert!(out["corrections"].as_array().unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn remote_backend_returns_upstream_status_on_client_error() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/api/spellcheck/1.0");
+            then.status(400).body("bad");
+        });
+        let tool = SpellcheckRemoteBackend::new(server.base_url());
+        let err = tool.call(&json!({"text":"Dia"})).await.unwrap_err();
+        assert!(err.to_string().contains("upstream status"));
+    }
+
     #[tokio::test]
     async fn remote_backend_returns_error_on_missing_text() {
         let tool = SpellcheckRemoteBackend::new("http://example");
         let err = tool.call(&serde_json::json!({})).await.unwrap_err();
-        assert!(err.contains("missing 'text'"));
+        assert!(err.to_string().contains("missing 'text'"));
     }
 
     #[tokio::test]
@@ -84,7 +314,7 @@ mod tests {
             .call(&serde_json::json!({"text": 123}))
             .await
             .unwrap_err();
-        assert!(err.contains("missing 'text'"));
+        assert!(err.to_string().contains("missing 'text'"));
     }
 
     #[test]
@@ -94,5 +324,7 @@ mod tests {
         assert!(t.description().contains("spellcheck"));
         let s = t.input_schema();
         assert_eq!(s["type"], "object");
+        let out = t.output_schema().unwrap();
+        assert!(out["properties"]["corrections"].is_object());
     }
 }
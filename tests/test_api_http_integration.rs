@@ -1,7 +1,7 @@
 use axum::body::{to_bytes, Body};
 use axum::{routing::post, Router};
 use hyper::Request;
-use irish_mcp_gateway::{api::mcp, tools::registry::build_registry}; // if lib target is unavailable, inline a copy of main's router in this test
+use irish_mcp_gateway::{api::mcp, infra::config::Config, tools::registry::build_registry}; // if lib target is unavailable, inline a copy of main's router in this test
 use serde_json::Value as J;
 use tower::ServiceExt;
 
@@ -11,7 +11,7 @@ const BODY_LIMIT: usize = 1024 * 1024;
 async fn http_e2e_tools_list_and_call() {
     let app = Router::new()
         .route("/mcp", post(mcp::http))
-        .with_state(build_registry());
+        .with_state(build_registry(&Config::default(), None));
 
     // list
     let list = Request::builder()
@@ -35,3 +35,135 @@ async fn http_e2e_tools_list_and_call() {
     let v: J = serde_json::from_slice(&bytes).unwrap();
     assert_eq!(v["result"]["corrections"], serde_json::Value::Array(vec![]));
 }
+
+#[tokio::test]
+async fn http_e2e_api_keys_guard_routes_and_scope_tools() {
+    use irish_mcp_gateway::infra::config::ApiKeyConfig;
+    use irish_mcp_gateway::infra::http::auth::hash_key;
+    use irish_mcp_gateway::infra::http_app::build_app_with_deprecated_api;
+    use irish_mcp_gateway::tools::registry::live::LiveRegistry;
+
+    let cfg = Config {
+        api_keys: vec![ApiKeyConfig {
+            name: "echo-only".into(),
+            sha256: hash_key("s3cret"),
+            scopes: Some(vec!["hello.echo".into()]),
+        }],
+        ..Config::default()
+    };
+    let app = build_app_with_deprecated_api(&LiveRegistry::new(cfg));
+    let rpc = |body: &'static str, key: Option<&str>| {
+        let mut req = Request::builder()
+            .method("POST")
+            .uri("/v1/grammar/check")
+            .header("content-type", "application/json");
+        if let Some(key) = key {
+            req = req.header("authorization", format!("Bearer {key}"));
+        }
+        req.body(Body::from(body)).unwrap()
+    };
+
+    // Health stays open
+    let health = Request::builder()
+        .uri("/healthz")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(app.clone().oneshot(health).await.unwrap().status(), 200);
+
+    // No key on either MCP surface
+    let list = r#"{"jsonrpc":"2.0","id":1,"method":"tools.list"}"#;
+    let resp = app.clone().oneshot(rpc(list, None)).await.unwrap();
+    assert_eq!(resp.status(), 401);
+    assert_eq!(resp.headers()["www-authenticate"], "Bearer");
+    let mcp = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header("content-type", "application/json")
+        .header("accept", "application/json, text/event-stream")
+        .body(Body::from(list))
+        .unwrap();
+    assert_eq!(app.clone().oneshot(mcp).await.unwrap().status(), 401);
+
+    // The key only sees and calls the tools in its scopes
+    let resp = app
+        .clone()
+        .oneshot(rpc(list, Some("s3cret")))
+        .await
+        .unwrap();
+    let bytes = to_bytes(resp.into_body(), BODY_LIMIT).await.unwrap();
+    let v: J = serde_json::from_slice(&bytes).unwrap();
+    let names: Vec<&str> = v["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["hello.echo"]);
+
+    let call = r#"{"jsonrpc":"2.0","id":2,"method":"tools.call","params":{"name":"gael.spellcheck.v1","arguments":{"text":"test"}}}"#;
+    let resp = app
+        .clone()
+        .oneshot(rpc(call, Some("s3cret")))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 403);
+    let bytes = to_bytes(resp.into_body(), BODY_LIMIT).await.unwrap();
+    let v: J = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(v["id"], 2);
+    assert_eq!(v["error"]["code"], -32007);
+}
+
+#[tokio::test]
+async fn http_e2e_oauth_protected_resource_metadata() {
+    use irish_mcp_gateway::infra::config::OAuthConfig;
+    use irish_mcp_gateway::infra::http_app::build_app_default;
+    use irish_mcp_gateway::tools::registry::live::LiveRegistry;
+
+    let metadata = || {
+        Request::builder()
+            .uri("/.well-known/oauth-protected-resource")
+            .body(Body::empty())
+            .unwrap()
+    };
+    let app = build_app_default(&LiveRegistry::new(Config::default()));
+    assert_eq!(app.oneshot(metadata()).await.unwrap().status(), 404);
+
+    let cfg = Config {
+        oauth: Some(OAuthConfig {
+            resource: "https://mcp.example.ie/mcp".into(),
+            issuer: "https://auth.example.ie".into(),
+            jwks_path: "/etc/irish-mcp/jwks.json".into(),
+            leeway_secs: 60,
+            scopes: [(
+                "mcp:grammar".to_string(),
+                vec!["gael.grammar_check".to_string()],
+            )]
+            .into(),
+        }),
+        ..Config::default()
+    };
+    let app = build_app_default(&LiveRegistry::new(cfg));
+    let resp = app.clone().oneshot(metadata()).await.unwrap();
+    assert_eq!(resp.status(), 200);
+    let bytes = to_bytes(resp.into_body(), BODY_LIMIT).await.unwrap();
+    let v: J = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(v["resource"], "https://mcp.example.ie/mcp");
+    assert_eq!(v["authorization_servers"][0], "https://auth.example.ie");
+    assert_eq!(v["scopes_supported"][0], "mcp:grammar");
+
+    // Unauthenticated MCP requests are pointed at the metadata
+    let mcp = Request::builder()
+        .method("POST")
+        .uri("/mcp")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#,
+        ))
+        .unwrap();
+    let resp = app.oneshot(mcp).await.unwrap();
+    assert_eq!(resp.status(), 401);
+    assert_eq!(
+        resp.headers()["www-authenticate"],
+        r#"Bearer resource_metadata="https://mcp.example.ie/.well-known/oauth-protected-resource""#
+    );
+}
@@ -7,13 +7,15 @@ use serde_json::{json, Value};
 use tokio::time::{timeout, Duration};
 use tower::ServiceExt; // for .oneshot
 
+use irish_mcp_gateway::infra::config::Config;
 use irish_mcp_gateway::infra::runtime::mcp_transport;
+use irish_mcp_gateway::tools::registry::live::LiveRegistry;
 
 static MCP_PROTOCOL_VERSION: &str = "0.5";
 
 #[tokio::test]
 async fn mcp_initialize_list_and_call_via_transport_and_tool_router() {
-    // Build a Router<Service> using the refactored transport and grammar tool router
+    // Build a Router<Service> using the transport and a registry pointed at the mock
     let server = httpmock::MockServer::start();
     server.mock(|when, then| {
         when.method(httpmock::Method::POST)
@@ -24,20 +26,13 @@ async fn mcp_initialize_list_and_call_via_transport_and_tool_router() {
         }]));
     });
 
-    let factory = {
-        let base = server.base_url();
-        move || {
-            let svc = irish_mcp_gateway::tools::grammar::tool_router::GrammarSvc {
-                checker: irish_mcp_gateway::clients::gramadoir::GramadoirRemote::new(base.clone()),
-            };
-            let tools: irish_mcp_gateway::tools::grammar::tool_router::GrammarRouter =
-                irish_mcp_gateway::tools::grammar::tool_router::GrammarSvc::router();
-            (svc, tools)
-        }
-    };
+    let mut cfg = Config::default();
+    cfg.gramadoir.base_url = Some(server.base_url());
+    let live = LiveRegistry::new(cfg);
+    let factory = irish_mcp_gateway::tools::registry::tool_router::registry_factory(live);
 
     let session_mgr = Arc::new(mcp_transport::LocalSessionManager::default());
-    let app = mcp_transport::make_streamable_http_service(factory, session_mgr);
+    let app = mcp_transport::make_streamable_http_handler_service(factory, session_mgr);
     let app = Router::new().route_service("/mcp", any_service(app));
 
     // Initialize
@@ -117,3 +112,177 @@ async fn mcp_initialize_list_and_call_via_transport_and_tool_router() {
         .expect("Did not find an rpcResponse for tools/call");
     assert!(v["result"]["structuredContent"]["issues"].is_array());
 }
+
+#[tokio::test]
+async fn mcp_registry_router_lists_and_calls_every_tool() {
+    let live = LiveRegistry::new(Config::default());
+    let expected: Vec<String> = live.registry().0.keys().map(|k| k.to_string()).collect();
+    let factory = irish_mcp_gateway::tools::registry::tool_router::registry_factory(live);
+
+    let session_mgr = Arc::new(mcp_transport::LocalSessionManager::default());
+    let app = mcp_transport::make_streamable_http_handler_service(factory, session_mgr);
+    let app = Router::new().route_service("/mcp", any_service(app));
+
+    let post = |body: Value, session: Option<&str>| {
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header(header::ACCEPT, "application/json, text/event-stream")
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(sid) = session {
+            builder = builder.header("MCP-Session-Id", sid);
+        }
+        builder
+            .body(axum::body::Body::from(body.to_string()))
+            .unwrap()
+    };
+    let sse_json = |bytes: &[u8]| -> Value {
+        String::from_utf8_lossy(bytes)
+            .lines()
+            .find_map(|line| line.strip_prefix("data: ").map(|d| d.to_string()))
+            .and_then(|d| serde_json::from_str::<Value>(&d).ok())
+            .expect("Did not find an rpcResponse")
+    };
+
+    let init = json!({
+        "jsonrpc":"2.0","id":1,"method":"initialize",
+        "params":{ "protocolVersion":"2025-03-26","capabilities":{},"clientInfo":{"name":"test","version":"0.1"} }
+    });
+    let init_res = app.clone().oneshot(post(init, None)).await.unwrap();
+    assert!(init_res.status().is_success());
+    let session_id = init_res
+        .headers()
+        .get("MCP-Session-Id")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_owned();
+
+    let initialized = json!({"jsonrpc":"2.0","method":"notifications/initialized","params":{}});
+    let res = app
+        .clone()
+        .oneshot(post(initialized, Some(&session_id)))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
+
+    let list = json!({"jsonrpc":"2.0","id":2,"method":"tools/list","params":{}});
+    let list_res = timeout(
+        Duration::from_secs(3),
+        app.clone().oneshot(post(list, Some(&session_id))),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    let bytes = list_res.into_body().collect().await.unwrap().to_bytes();
+    let v = sse_json(&bytes);
+    let names: Vec<&str> = v["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    for name in &expected {
+        assert!(names.contains(&name.as_str()), "missing {name}");
+    }
+    for tool in v["result"]["tools"].as_array().unwrap() {
+        assert_eq!(tool["outputSchema"]["type"], "object");
+    }
+
+    let call = json!({
+        "jsonrpc":"2.0","id":3,"method":"tools/call",
+        "params": {"name":"hello.echo","arguments":{"name":"Aoife"}}
+    });
+    let call_res = app
+        .clone()
+        .oneshot(post(call, Some(&session_id)))
+        .await
+        .unwrap();
+    let bytes = call_res.into_body().collect().await.unwrap().to_bytes();
+    let v = sse_json(&bytes);
+    assert_eq!(
+        v["result"]["structuredContent"]["message"],
+        "Dia dhuit, Aoife!"
+    );
+}
+
+#[tokio::test]
+async fn mcp_tools_list_is_filtered_by_api_key_scopes() {
+    use irish_mcp_gateway::infra::config::ApiKeyConfig;
+    use irish_mcp_gateway::infra::http::auth::hash_key;
+
+    let cfg = Config {
+        api_keys: vec![ApiKeyConfig {
+            name: "echo-only".into(),
+            sha256: hash_key("s3cret"),
+            scopes: Some(vec!["hello.echo".into()]),
+        }],
+        ..Config::default()
+    };
+    let app = irish_mcp_gateway::infra::http_app::build_app_default(&LiveRegistry::new(cfg));
+    let post = |body: Value, session: Option<&str>| {
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header(header::ACCEPT, "application/json, text/event-stream")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-api-key", "s3cret");
+        if let Some(sid) = session {
+            builder = builder.header("MCP-Session-Id", sid);
+        }
+        builder
+            .body(axum::body::Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    let init = json!({
+        "jsonrpc":"2.0","id":1,"method":"initialize",
+        "params":{ "protocolVersion":"2025-03-26","capabilities":{},"clientInfo":{"name":"test","version":"0.1"} }
+    });
+    let init_res = app.clone().oneshot(post(init, None)).await.unwrap();
+    assert!(init_res.status().is_success());
+    let session_id = init_res.headers()["MCP-Session-Id"]
+        .to_str()
+        .unwrap()
+        .to_owned();
+    let initialized = json!({"jsonrpc":"2.0","method":"notifications/initialized","params":{}});
+    let res = app
+        .clone()
+        .oneshot(post(initialized, Some(&session_id)))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
+
+    let list = json!({"jsonrpc":"2.0","id":2,"method":"tools/list","params":{}});
+    let list_res = timeout(
+        Duration::from_secs(3),
+        app.clone().oneshot(post(list, Some(&session_id))),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    let bytes = list_res.into_body().collect().await.unwrap().to_bytes();
+    let v: Value = String::from_utf8_lossy(&bytes)
+        .lines()
+        .find_map(|line| line.strip_prefix("data: "))
+        .and_then(|d| serde_json::from_str(d).ok())
+        .expect("Did not find an rpcResponse");
+    let names: Vec<&str> = v["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["hello.echo"]);
+
+    let call = json!({
+        "jsonrpc":"2.0","id":3,"method":"tools/call",
+        "params": {"name":"gael.spellcheck.v1","arguments":{"text":"test"}}
+    });
+    let res = app
+        .clone()
+        .oneshot(post(call, Some(&session_id)))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}